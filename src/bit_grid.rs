@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use crate::boundary::BoundaryMode;
+use crate::rule::Rule;
+use crate::tile::{ALIVE, DEAD};
+
+const WORD_BITS: usize = 64;
+
+/// A dense grid packing 64 cells into every `u64`, bit `i` of word `w` in a row is the
+/// cell at `x = w * 64 + i`.
+///
+/// Generations rules keep a second bit plane marking dying cells, which can't be born
+/// into, with their `Tile` states held on the side since there are usually few of them.
+pub struct BitGrid {
+    pub x_size: usize,
+    pub y_size: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+    dying_words: Vec<u64>,
+    dying_states: HashMap<(usize, usize), u8>,
+}
+
+impl BitGrid {
+    pub fn new(x_size: usize, y_size: usize) -> BitGrid {
+        let words_per_row = x_size.div_ceil(WORD_BITS);
+        return BitGrid {
+            x_size,
+            y_size,
+            words_per_row,
+            words: vec![0; words_per_row * y_size],
+            dying_words: vec![0; words_per_row * y_size],
+            dying_states: HashMap::new(),
+        };
+    }
+
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        if x >= self.x_size || y >= self.y_size {
+            return false;
+        }
+        let word = self.words[y * self.words_per_row + x / WORD_BITS];
+        return (word >> (x % WORD_BITS)) & 1 == 1;
+    }
+
+    pub fn cell_state(&self, x: usize, y: usize) -> u8 {
+        if self.is_alive(x, y) {
+            return ALIVE;
+        }
+        return *self.dying_states.get(&(x, y)).unwrap_or(&DEAD);
+    }
+
+    pub fn set_alive(&mut self, x: usize, y: usize, alive: bool) {
+        let index = y * self.words_per_row + x / WORD_BITS;
+        let bit = 1_u64 << (x % WORD_BITS);
+        if alive {
+            self.words[index] |= bit;
+        } else {
+            self.words[index] &= !bit;
+        }
+    }
+
+    /// Puts a cell into one of the dying states, as read back from a snapshot.
+    pub fn set_dying(&mut self, x: usize, y: usize, state: u8) {
+        self.set_alive(x, y, false);
+        self.dying_words[y * self.words_per_row + x / WORD_BITS] |= 1 << (x % WORD_BITS);
+        self.dying_states.insert((x, y), state);
+    }
+
+    /// Every dying cell with its state, in no particular order.
+    pub fn dying_cells(&self) -> Vec<((usize, usize), u8)> {
+        return self.dying_states.iter().map(|(key, state)| (*key, *state)).collect();
+    }
+
+    pub fn population(&self) -> usize {
+        return self.words.iter().map(|word| word.count_ones() as usize).sum();
+    }
+
+    pub fn live_cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for y in 0..self.y_size {
+            for word_index in 0..self.words_per_row {
+                let mut word = self.words[y * self.words_per_row + word_index];
+                while word != 0 {
+                    let bit = word.trailing_zeros() as usize;
+                    cells.push((word_index * WORD_BITS + bit, y));
+                    word &= word - 1;
+                }
+            }
+        }
+        return cells;
+    }
+
+    pub fn handle_generation(&mut self, rule: &Rule, boundary_mode: BoundaryMode) {
+        let mut next_words = vec![0; self.words.len()];
+        let mut neighbors: Vec<Vec<u64>> = vec![vec![0; self.words_per_row]; 8];
+        let mut count = [0_u64; 4];
+        for y in 0..self.y_size {
+            let mut index = 0;
+            for y_offset in -1..=1 {
+                for x_offset in -1..=1 {
+                    if x_offset == 0 && y_offset == 0 {
+                        continue;
+                    }
+                    self.shifted_row(
+                        &mut neighbors[index],
+                        x_offset,
+                        y as i64 + y_offset,
+                        boundary_mode,
+                    );
+                    index += 1;
+                }
+            }
+
+            let row_start = y * self.words_per_row;
+            for word_index in 0..self.words_per_row {
+                count.iter_mut().for_each(|bit_plane| *bit_plane = 0);
+                for neighbor_row in neighbors.iter() {
+                    BitGrid::add_to_count(&mut count, neighbor_row[word_index]);
+                }
+
+                let alive = self.words[row_start + word_index];
+                let empty = !alive & !self.dying_words[row_start + word_index];
+                let mut next = 0;
+                for live_neighbors in 0..=8 {
+                    let survive = if rule.survives(live_neighbors) { alive } else { 0 };
+                    let born = if rule.is_born(live_neighbors) { empty } else { 0 };
+                    next |= BitGrid::count_equals(&count, live_neighbors) & (survive | born);
+                }
+                next_words[row_start + word_index] = next & self.word_mask(word_index);
+            }
+        }
+
+        if rule.is_generations() {
+            self.handle_dying_generation(rule, &next_words);
+        }
+        self.words = next_words;
+    }
+
+    /// Fades the cells that were already dying and starts the ones that just failed to survive.
+    fn handle_dying_generation(&mut self, rule: &Rule, next_words: &[u64]) {
+        let mut next_dying_states = HashMap::new();
+        for (key, state) in self.dying_states.iter() {
+            let next_state = rule.next_state(*state, 0);
+            if next_state != DEAD {
+                next_dying_states.insert(*key, next_state);
+            }
+        }
+        for (index, (alive, next)) in self.words.iter().zip(next_words).enumerate() {
+            let mut died = alive & !next;
+            while died != 0 {
+                let bit = died.trailing_zeros() as usize;
+                let x = (index % self.words_per_row) * WORD_BITS + bit;
+                next_dying_states.insert((x, index / self.words_per_row), ALIVE + 1);
+                died &= died - 1;
+            }
+        }
+
+        self.dying_words.iter_mut().for_each(|word| *word = 0);
+        for (x, y) in next_dying_states.keys() {
+            self.dying_words[y * self.words_per_row + x / WORD_BITS] |= 1 << (x % WORD_BITS);
+        }
+        self.dying_states = next_dying_states;
+    }
+
+    /// Adds one bit per cell onto a 4 bit counter stored as bit planes, a chain of half
+    /// adders running over 64 cells at once.
+    fn add_to_count(count: &mut [u64; 4], bits: u64) {
+        let mut carry = bits;
+        for bit_plane in count.iter_mut() {
+            let next_carry = *bit_plane & carry;
+            *bit_plane ^= carry;
+            carry = next_carry;
+        }
+    }
+
+    /// Mask of the cells whose counter equals `value`.
+    fn count_equals(count: &[u64; 4], value: usize) -> u64 {
+        let mut mask = !0;
+        for (bit, bit_plane) in count.iter().enumerate() {
+            if (value >> bit) & 1 == 1 {
+                mask &= *bit_plane;
+            } else {
+                mask &= !*bit_plane;
+            }
+        }
+        return mask;
+    }
+
+    /// Mask of the bits in `word_index` that are inside the grid.
+    fn word_mask(&self, word_index: usize) -> u64 {
+        let used_bits = self.x_size - word_index * WORD_BITS;
+        if used_bits >= WORD_BITS {
+            return !0;
+        }
+        return (1_u64 << used_bits) - 1;
+    }
+
+    fn resolved_cell(&self, x: i64, y: i64, boundary_mode: BoundaryMode) -> bool {
+        return match boundary_mode.resolve(x, y, self.x_size, self.y_size) {
+            Some((x, y)) => self.is_alive(x, y),
+            None => false,
+        };
+    }
+
+    /// Fills `out` so bit `x` holds the cell at `(x + x_offset, y)` after the boundary has
+    /// been applied.
+    fn shifted_row(&self, out: &mut [u64], x_offset: i64, y: i64, boundary_mode: BoundaryMode) {
+        if y >= 0 && (y as usize) < self.y_size {
+            let row_start = y as usize * self.words_per_row;
+            out.copy_from_slice(&self.words[row_start..row_start + self.words_per_row]);
+        } else {
+            // Rows past the top or bottom only exist through the boundary, which may mirror them.
+            out.iter_mut().for_each(|word| *word = 0);
+            if boundary_mode != BoundaryMode::Dead {
+                for x in 0..self.x_size {
+                    if self.resolved_cell(x as i64, y, boundary_mode) {
+                        out[x / WORD_BITS] |= 1 << (x % WORD_BITS);
+                    }
+                }
+            }
+        }
+
+        let last_x = self.x_size - 1;
+        match x_offset {
+            -1 => {
+                let mut carry = 0;
+                for word in out.iter_mut() {
+                    let next_carry = *word >> (WORD_BITS - 1);
+                    *word = (*word << 1) | carry;
+                    carry = next_carry;
+                }
+                if self.resolved_cell(-1, y, boundary_mode) {
+                    out[0] |= 1;
+                }
+            }
+            1 => {
+                let mut carry = 0;
+                for word in out.iter_mut().rev() {
+                    let next_carry = *word << (WORD_BITS - 1);
+                    *word = (*word >> 1) | carry;
+                    carry = next_carry;
+                }
+                let last_bit = 1 << (last_x % WORD_BITS);
+                if self.resolved_cell(self.x_size as i64, y, boundary_mode) {
+                    out[last_x / WORD_BITS] |= last_bit;
+                } else {
+                    out[last_x / WORD_BITS] &= !last_bit;
+                }
+            }
+            _ => {}
+        }
+    }
+}
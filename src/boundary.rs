@@ -0,0 +1,87 @@
+/// How neighbor lookups behave once they step past the edge of a bounded world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoundaryMode {
+    /// Everything past the edge is permanently dead.
+    #[default]
+    Dead,
+    /// Both edges wrap straight around to the opposite side.
+    Torus,
+    /// Left/right wrap straight around, top/bottom wrap with the x axis mirrored.
+    KleinBottle,
+    /// Both edges wrap with the other axis mirrored (a real projective plane).
+    CrossSurface,
+}
+
+impl BoundaryMode {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            BoundaryMode::Dead => "dead",
+            BoundaryMode::Torus => "torus",
+            BoundaryMode::KleinBottle => "klein",
+            BoundaryMode::CrossSurface => "cross",
+        };
+    }
+
+    /// The mode a `name()` came from.
+    pub fn from_name(name: &str) -> Option<BoundaryMode> {
+        return match name {
+            "dead" => Some(BoundaryMode::Dead),
+            "torus" => Some(BoundaryMode::Torus),
+            "klein" => Some(BoundaryMode::KleinBottle),
+            "cross" => Some(BoundaryMode::CrossSurface),
+            _ => None,
+        };
+    }
+
+    pub fn next(&self) -> BoundaryMode {
+        return match self {
+            BoundaryMode::Dead => BoundaryMode::Torus,
+            BoundaryMode::Torus => BoundaryMode::KleinBottle,
+            BoundaryMode::KleinBottle => BoundaryMode::CrossSurface,
+            BoundaryMode::CrossSurface => BoundaryMode::Dead,
+        };
+    }
+
+    /// Maps a possibly out of range coordinate back onto the `x_size` x `y_size` grid,
+    /// returning `None` when it falls off a dead edge.
+    pub fn resolve(
+        &self,
+        x: i64,
+        y: i64,
+        x_size: usize,
+        y_size: usize,
+    ) -> Option<(usize, usize)> {
+        let width = x_size as i64;
+        let height = y_size as i64;
+        if x >= 0 && x < width && y >= 0 && y < height {
+            return Some((x as usize, y as usize));
+        }
+
+        let (mut x, mut y) = (x, y);
+        match self {
+            BoundaryMode::Dead => return None,
+            BoundaryMode::Torus => {}
+            BoundaryMode::KleinBottle => {
+                if BoundaryMode::wraps_an_odd_number_of_times(y, height) {
+                    x = width - 1 - x;
+                }
+            }
+            BoundaryMode::CrossSurface => {
+                let flip_x = BoundaryMode::wraps_an_odd_number_of_times(y, height);
+                let flip_y = BoundaryMode::wraps_an_odd_number_of_times(x, width);
+                if flip_x {
+                    x = width - 1 - x;
+                }
+                if flip_y {
+                    y = height - 1 - y;
+                }
+            }
+        }
+
+        return Some((x.rem_euclid(width) as usize, y.rem_euclid(height) as usize));
+    }
+
+    fn wraps_an_odd_number_of_times(value: i64, size: i64) -> bool {
+        return value.div_euclid(size).rem_euclid(2) == 1;
+    }
+}
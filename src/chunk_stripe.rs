@@ -0,0 +1,155 @@
+use std::thread;
+use crate::boundary::BoundaryMode;
+use crate::rule::Rule;
+use crate::tile::ALIVE;
+
+/// A horizontal band of rows, stored with a one cell halo on every side so it can be
+/// stepped on its own thread without touching the rest of the world.
+pub struct ChunkStripe {
+    pub y_start: usize,
+    pub rows: usize,
+    x_size: usize,
+    cells: Vec<u8>,
+}
+
+impl ChunkStripe {
+    /// How many rows each of `stripe_count` stripes gets, all but the last stripe are the
+    /// same height.
+    pub fn rows_per_stripe(y_size: usize, stripe_count: usize) -> usize {
+        return y_size.div_ceil(stripe_count.max(1)).max(1);
+    }
+
+    /// Splits a `x_size` x `y_size` world into at most `stripe_count` stripes of
+    /// `rows_per_stripe` rows, the last stripe is shorter when the height doesn't divide evenly.
+    pub fn split(x_size: usize, y_size: usize, stripe_count: usize) -> Vec<ChunkStripe> {
+        let stripe_rows = ChunkStripe::rows_per_stripe(y_size, stripe_count);
+        let mut stripes = Vec::new();
+        let mut y_start = 0;
+        while y_start < y_size {
+            let rows = stripe_rows.min(y_size - y_start);
+            stripes.push(ChunkStripe {
+                y_start,
+                rows,
+                x_size,
+                cells: vec![0; (rows + 2) * (x_size + 2)],
+            });
+            y_start += rows;
+        }
+        return stripes;
+    }
+
+    fn index(&self, x: i64, local_y: i64) -> usize {
+        return (local_y + 1) as usize * (self.x_size + 2) + (x + 1) as usize;
+    }
+
+    fn set(&mut self, x: i64, local_y: i64, value: u8) {
+        let index = self.index(x, local_y);
+        self.cells[index] = value;
+    }
+
+    fn get(&self, x: i64, local_y: i64) -> u8 {
+        return self.cells[self.index(x, local_y)];
+    }
+
+    /// Scatters the `Tile` states of every live or dying cell into the stripes that own them.
+    pub fn fill<I: Iterator<Item = ((usize, usize), u8)>>(
+        stripes: &mut [ChunkStripe],
+        stripe_rows: usize,
+        occupied_cells: I,
+    ) {
+        for ((x, y), state) in occupied_cells {
+            let stripe = &mut stripes[y / stripe_rows];
+            let local_y = (y - stripe.y_start) as i64;
+            stripe.set(x as i64, local_y, state);
+        }
+    }
+
+    /// Copies the cells bordering every stripe into its halo, following the boundary mode
+    /// across the outer edges of the world.
+    pub fn exchange_halos(
+        stripes: &mut [ChunkStripe],
+        stripe_rows: usize,
+        y_size: usize,
+        boundary_mode: BoundaryMode,
+    ) {
+        let mut halos: Vec<Vec<(i64, i64, u8)>> = Vec::new();
+        for stripe in stripes.iter() {
+            let x_size = stripe.x_size;
+            let mut halo = Vec::new();
+            for local_y in -1..=(stripe.rows as i64) {
+                let is_halo_row = local_y == -1 || local_y == stripe.rows as i64;
+                for x in -1..=(x_size as i64) {
+                    let is_halo_column = x == -1 || x == x_size as i64;
+                    if !is_halo_row && !is_halo_column {
+                        continue;
+                    }
+                    let y = stripe.y_start as i64 + local_y;
+                    let value = match boundary_mode.resolve(x, y, x_size, y_size) {
+                        Some((owner_x, owner_y)) => {
+                            let owner = &stripes[owner_y / stripe_rows];
+                            owner.get(owner_x as i64, (owner_y - owner.y_start) as i64)
+                        }
+                        None => 0,
+                    };
+                    halo.push((x, local_y, value));
+                }
+            }
+            halos.push(halo);
+        }
+
+        for (stripe, halo) in stripes.iter_mut().zip(halos) {
+            for (x, local_y, value) in halo {
+                stripe.set(x, local_y, value);
+            }
+        }
+    }
+
+    /// Every cell of the stripe that changes state next generation, as `(x, y, state)`.
+    pub fn changes(&self, rule: &Rule) -> Vec<(usize, usize, u8)> {
+        let mut changes = Vec::new();
+        for local_y in 0..self.rows as i64 {
+            for x in 0..self.x_size as i64 {
+                let mut live_neighbors = 0;
+                for y_offset in -1..=1 {
+                    for x_offset in -1..=1 {
+                        if (x_offset != 0 || y_offset != 0)
+                            && self.get(x + x_offset, local_y + y_offset) == ALIVE
+                        {
+                            live_neighbors += 1;
+                        }
+                    }
+                }
+
+                let state = self.get(x, local_y);
+                let next_state = rule.next_state(state, live_neighbors);
+                if state != next_state {
+                    changes.push((x as usize, self.y_start + local_y as usize, next_state));
+                }
+            }
+        }
+        return changes;
+    }
+
+    /// Steps the stripes on up to `worker_threads` threads and gathers their changes.
+    pub fn parallel_changes(
+        stripes: &[ChunkStripe],
+        rule: &Rule,
+        worker_threads: usize,
+    ) -> Vec<(usize, usize, u8)> {
+        let stripes_per_thread = stripes.len().div_ceil(worker_threads.max(1)).max(1);
+        return thread::scope(|scope| {
+            let handles: Vec<_> = stripes
+                .chunks(stripes_per_thread)
+                .map(|group| {
+                    scope.spawn(move || {
+                        group.iter().flat_map(|stripe| stripe.changes(rule)).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+use std::path::PathBuf;
+use crate::boundary::BoundaryMode;
+use crate::rule::{Rule, RuleParseError};
+use crate::world::{StorageKind, DEFAULT_PAN_STEP, MAX_FRAME_INTERVAL_MS, MAX_WORLD_SIZE};
+
+/// The smallest world that still leaves the design map a cell to draw on inside its border.
+const MIN_WORLD_SIZE: usize = 3;
+
+pub const USAGE: &str = "\
+Usage: game_of_life [OPTIONS]
+
+Options:
+  --width <CELLS>         world width, 3 to 4096 (default 250)
+  --height <CELLS>        world height, 3 to 4096 (default 250)
+  --chunk-width <CELLS>   visible chunk width, at most the world width (default 75)
+  --chunk-height <CELLS>  visible chunk height, at most the world height (default 50)
+  --interval-ms <MS>      requested time between generations, at most 2000 (default 36)
+  --rule <RULE>           rulestring such as B3/S23, 23/3 or B2/S/C3 (default: the pattern's rule, or B3/S23)
+  --boundary <EDGE>       dead, torus, klein or cross (default dead)
+  --storage <KIND>        tiles, bits or unbounded (default tiles)
+  --threads <COUNT>       worker threads for stepping (default: all cores)
+  --pan-step <CELLS>      cells the view moves per w/a/s/d press while playing (default 8)
+  --pattern <FILE>        .rle, .cells, .mc or Life 1.05/1.06 file loaded into the centre of the design map
+  --seed <NUMBER>         fill the design map with a reproducible random soup
+  -h, --help              print this help and exit
+";
+
+/// Everything the game can be configured with from the command line.
+#[derive(Debug, PartialEq)]
+pub struct CliArgs {
+    pub width: usize,
+    pub height: usize,
+    pub chunk_width: usize,
+    pub chunk_height: usize,
+    pub interval_ms: usize,
+    /// `None` uses the rule from the `--pattern` file, or Conway's Life without one.
+    pub rule: Option<Rule>,
+    pub boundary_mode: BoundaryMode,
+    pub storage_kind: StorageKind,
+    pub worker_threads: Option<usize>,
+    pub pan_step: usize,
+    pub pattern: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub show_help: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    UnknownArgument(String),
+    MissingValue(String),
+    InvalidNumber { flag: String, value: String },
+    InvalidRule(RuleParseError),
+    InvalidChoice { flag: String, value: String, choices: &'static str },
+    TooSmall { flag: String, minimum: usize },
+    TooLarge { flag: String, maximum: usize },
+    ChunkLargerThanWorld { flag: String },
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownArgument(argument) => write!(f, "unknown argument '{}'", argument),
+            CliError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            CliError::InvalidNumber { flag, value } => {
+                write!(f, "{} expects a whole number, found '{}'", flag, value)
+            }
+            CliError::InvalidRule(error) => write!(f, "--rule: {}", error),
+            CliError::InvalidChoice { flag, value, choices } => {
+                write!(f, "{} must be one of {}, found '{}'", flag, choices, value)
+            }
+            CliError::TooSmall { flag, minimum } => write!(f, "{} must be at least {}", flag, minimum),
+            CliError::TooLarge { flag, maximum } => write!(f, "{} must be at most {}", flag, maximum),
+            CliError::ChunkLargerThanWorld { flag } => {
+                write!(f, "{} can't be larger than the world", flag)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl Default for CliArgs {
+    fn default() -> CliArgs {
+        return CliArgs {
+            width: 250,
+            height: 250,
+            chunk_width: 75,
+            chunk_height: 50,
+            interval_ms: 36,
+            rule: None,
+            boundary_mode: BoundaryMode::Dead,
+            storage_kind: StorageKind::Tiles,
+            worker_threads: None,
+            pan_step: DEFAULT_PAN_STEP,
+            pattern: None,
+            seed: None,
+            show_help: false,
+        };
+    }
+}
+
+impl CliArgs {
+    /// Parses the arguments after the program name, accepting both `--flag value` and
+    /// `--flag=value`.
+    pub fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<CliArgs, CliError> {
+        let mut cli_args = CliArgs::default();
+        let mut arguments = arguments.into_iter();
+        while let Some(argument) = arguments.next() {
+            if argument == "-h" || argument == "--help" {
+                cli_args.show_help = true;
+                return Ok(cli_args);
+            }
+
+            let (flag, inline_value) = match argument.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (argument.clone(), None),
+            };
+            if !flag.starts_with("--") {
+                return Err(CliError::UnknownArgument(argument));
+            }
+            let value = match inline_value.or_else(|| arguments.next()) {
+                Some(value) => value,
+                None => return Err(CliError::MissingValue(flag)),
+            };
+
+            match flag.as_str() {
+                "--width" => cli_args.width = CliArgs::parse_number(&flag, &value)?,
+                "--height" => cli_args.height = CliArgs::parse_number(&flag, &value)?,
+                "--chunk-width" => cli_args.chunk_width = CliArgs::parse_number(&flag, &value)?,
+                "--chunk-height" => cli_args.chunk_height = CliArgs::parse_number(&flag, &value)?,
+                "--interval-ms" => cli_args.interval_ms = CliArgs::parse_number(&flag, &value)?,
+                "--threads" => {
+                    let threads = CliArgs::parse_number(&flag, &value)?;
+                    if threads == 0 {
+                        return Err(CliError::TooSmall { flag, minimum: 1 });
+                    }
+                    cli_args.worker_threads = Some(threads);
+                }
+                "--pan-step" => cli_args.pan_step = CliArgs::parse_number(&flag, &value)?,
+                "--seed" => cli_args.seed = Some(CliArgs::parse_number(&flag, &value)?),
+                "--rule" => cli_args.rule = Some(Rule::parse(&value).map_err(CliError::InvalidRule)?),
+                "--pattern" => cli_args.pattern = Some(PathBuf::from(value)),
+                "--boundary" => {
+                    cli_args.boundary_mode = match BoundaryMode::from_name(&value) {
+                        Some(boundary_mode) => boundary_mode,
+                        None => {
+                            return Err(CliError::InvalidChoice {
+                                flag,
+                                value,
+                                choices: "dead, torus, klein, cross",
+                            })
+                        }
+                    }
+                }
+                "--storage" => {
+                    cli_args.storage_kind = match StorageKind::from_name(&value) {
+                        Some(storage_kind) => storage_kind,
+                        None => {
+                            return Err(CliError::InvalidChoice {
+                                flag,
+                                value,
+                                choices: "tiles, bits, unbounded",
+                            })
+                        }
+                    }
+                }
+                _ => return Err(CliError::UnknownArgument(flag)),
+            }
+        }
+
+        cli_args.validate()?;
+        return Ok(cli_args);
+    }
+
+    fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+        return value.parse::<T>().map_err(|_| CliError::InvalidNumber {
+            flag: flag.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    fn validate(&self) -> Result<(), CliError> {
+        // The chunk sizes are bounded by the world below.
+        let sizes = [
+            ("--width", self.width, MIN_WORLD_SIZE, MAX_WORLD_SIZE),
+            ("--height", self.height, MIN_WORLD_SIZE, MAX_WORLD_SIZE),
+            ("--chunk-width", self.chunk_width, 1, usize::MAX),
+            ("--chunk-height", self.chunk_height, 1, usize::MAX),
+            ("--interval-ms", self.interval_ms, 0, MAX_FRAME_INTERVAL_MS),
+            ("--pan-step", self.pan_step, 1, MAX_WORLD_SIZE),
+        ];
+        for (flag, size, minimum, maximum) in sizes {
+            if size < minimum {
+                return Err(CliError::TooSmall { flag: flag.to_string(), minimum });
+            }
+            if size > maximum {
+                return Err(CliError::TooLarge { flag: flag.to_string(), maximum });
+            }
+        }
+        if self.chunk_width > self.width {
+            return Err(CliError::ChunkLargerThanWorld { flag: "--chunk-width".to_string() });
+        }
+        if self.chunk_height > self.height {
+            return Err(CliError::ChunkLargerThanWorld { flag: "--chunk-height".to_string() });
+        }
+        return Ok(());
+    }
+}
@@ -1,4 +1,9 @@
-﻿use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use crate::drawing::{line, DrawTool};
+use crate::edit_history::{CellChange, EditHistory};
+use crate::pattern_file::Pattern;
+use crate::random::SeededRandom;
+use crate::transform::Transform;
 use crate::world_display::MoveDirections;
 
 pub struct DesignWorld {
@@ -6,8 +11,26 @@ pub struct DesignWorld {
     pub max_x: u16,
     pub max_y: u16,
     pub marked_positions: HashMap<(u16, u16), bool>,
+    /// A pattern about to be stamped, drawn with its top left corner on the cursor.
+    pub preview: Option<Pattern>,
+    /// Where a selection was started, the selection is the rectangle from here to the cursor.
+    pub selection_anchor: Option<(u16, u16)>,
+    /// Marks copied or cut out of a selection, relative to its top left corner.
+    pub clipboard: Option<Pattern>,
+    /// Every edit to the marks, so they can be undone and redone.
+    pub history: EditHistory,
+    pub tool: DrawTool,
+    /// Where the shape being drawn was started, it ends at the cursor.
+    pub tool_anchor: Option<(u16, u16)>,
+    pub pen_down: bool,
+    /// Marks a smaller map no longer had room for, put back once a resize makes room again.
+    pub clipped_marks: Vec<(u16, u16)>,
 }
 
+/// How many edits, and how many changed cells across them, the undo history keeps.
+pub const MAX_UNDO_EDITS: usize = 200;
+pub const MAX_UNDO_CHANGES: usize = 1_000_000;
+
 impl DesignWorld {
     pub fn new(max_x: u16, max_y: u16) -> DesignWorld {
         let mut marked_positions: HashMap<(u16, u16), bool> = HashMap::new();
@@ -22,6 +45,14 @@ impl DesignWorld {
             max_x,
             max_y,
             marked_positions,
+            preview: None,
+            selection_anchor: None,
+            clipboard: None,
+            history: EditHistory::new(MAX_UNDO_EDITS, MAX_UNDO_CHANGES),
+            tool: DrawTool::default(),
+            tool_anchor: None,
+            pen_down: false,
+            clipped_marks: Vec::new(),
         };
     }
 
@@ -58,4 +89,388 @@ impl DesignWorld {
 
         self.current_position = (new_x as u16, new_y as u16);
     }
+
+    /// Changes the map size, keeping the marks that are still inside the border and setting
+    /// the rest aside, and pulling the cursor back in if it fell off. The undo history
+    /// carries over, undoing an edit to a position set aside updates the set aside mark.
+    pub fn resize(&mut self, max_x: u16, max_y: u16) {
+        let mut resized = DesignWorld::new(max_x, max_y);
+        let marks = self
+            .marked_positions
+            .iter()
+            .filter(|(_, marked)| **marked)
+            .map(|(position, _)| *position)
+            .chain(self.clipped_marks.drain(..));
+        for position in marks {
+            if resized.is_editable(position) {
+                resized.marked_positions.insert(position, true);
+            } else {
+                resized.clipped_marks.push(position);
+            }
+        }
+        resized.preview = self.preview.take();
+        resized.clipboard = self.clipboard.take();
+        resized.tool = self.tool;
+        std::mem::swap(&mut resized.history, &mut self.history);
+        resized.current_position = (
+            self.current_position.0.min(max_x - 2),
+            self.current_position.1.min(max_y - 2),
+        );
+        *self = resized;
+    }
+
+    /// Whether a position is inside the border and can be marked.
+    pub fn is_editable(&self, position: (u16, u16)) -> bool {
+        return position.0 > 0
+            && position.1 > 0
+            && position.0 < self.max_x - 1
+            && position.1 < self.max_y - 1;
+    }
+
+    pub fn is_marked(&self, position: (u16, u16)) -> bool {
+        return *self.marked_positions.get(&position).unwrap_or(&false);
+    }
+
+    /// Sets one mark, recording the change in the edit in progress. Every edit goes through
+    /// here and finishes with `history.commit()` so it becomes a single undo step.
+    pub fn set_mark(&mut self, position: (u16, u16), marked: bool) {
+        let before = self.is_marked(position);
+        if before == marked {
+            return;
+        }
+        self.marked_positions.insert(position, marked);
+        self.history.record(CellChange { position, before, after: marked });
+    }
+
+    /// Flips the mark under the cursor.
+    pub fn toggle_mark(&mut self) {
+        let position = self.current_position;
+        self.set_mark(position, !self.is_marked(position));
+        self.history.commit();
+    }
+
+    /// Puts back the marks from before the last edit, returns false when there's nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.history.commit();
+        let Some(edit) = self.history.undo() else {
+            return false;
+        };
+        let changes: Vec<CellChange> = edit.iter().rev().copied().collect();
+        for change in changes {
+            self.put_mark(change.position, change.before);
+        }
+        return true;
+    }
+
+    /// Reapplies the last undone edit, returns false when there's nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.history.commit();
+        let Some(edit) = self.history.redo() else {
+            return false;
+        };
+        let changes: Vec<CellChange> = edit.clone();
+        for change in changes {
+            self.put_mark(change.position, change.after);
+        }
+        return true;
+    }
+
+    /// Sets a mark without recording it, a position the map is too small for right now is
+    /// updated in `clipped_marks` instead.
+    fn put_mark(&mut self, position: (u16, u16), marked: bool) {
+        if self.is_editable(position) {
+            self.marked_positions.insert(position, marked);
+            return;
+        }
+        self.clipped_marks.retain(|clipped| *clipped != position);
+        if marked {
+            self.clipped_marks.push(position);
+        }
+    }
+
+    /// Marks the live cells of `pattern` with its top left corner at `origin`, cells that
+    /// land on or past the border are dropped.
+    pub fn stamp(&mut self, pattern: &Pattern, origin: (u16, u16)) {
+        for (offset, alive) in pattern.iter() {
+            let position = (
+                origin.0.saturating_add(offset.0),
+                origin.1.saturating_add(offset.1),
+            );
+            if *alive && self.is_editable(position) {
+                self.set_mark(position, true);
+            }
+        }
+        self.history.commit();
+    }
+
+    /// The top left and bottom right corners of the selection, inclusive.
+    pub fn selection(&self) -> Option<((u16, u16), (u16, u16))> {
+        let anchor = self.selection_anchor?;
+        let cursor = self.current_position;
+        return Some((
+            (anchor.0.min(cursor.0), anchor.1.min(cursor.1)),
+            (anchor.0.max(cursor.0), anchor.1.max(cursor.1)),
+        ));
+    }
+
+    pub fn is_selected(&self, position: (u16, u16)) -> bool {
+        return match self.selection() {
+            Some((top_left, bottom_right)) => {
+                (top_left.0..=bottom_right.0).contains(&position.0)
+                    && (top_left.1..=bottom_right.1).contains(&position.1)
+            }
+            None => false,
+        };
+    }
+
+    /// Copies the marks inside the selection to the clipboard and ends the selection.
+    pub fn copy_selection(&mut self) {
+        let Some((top_left, _)) = self.selection() else {
+            return;
+        };
+        let mut clipboard = Pattern::new();
+        for (position, marked) in self.marked_positions.iter() {
+            if *marked && self.is_selected(*position) {
+                clipboard.insert((position.0 - top_left.0, position.1 - top_left.1), true);
+            }
+        }
+        self.clipboard = Some(clipboard);
+        self.selection_anchor = None;
+    }
+
+    /// Copies the selection to the clipboard and clears it from the map.
+    pub fn cut_selection(&mut self) {
+        let Some(selection) = self.selection() else {
+            return;
+        };
+        self.copy_selection();
+        self.update_region(selection, |_| false);
+    }
+
+    /// The selection, or the whole map inside the border when nothing is selected.
+    pub fn edit_region(&self) -> ((u16, u16), (u16, u16)) {
+        return self
+            .selection()
+            .unwrap_or(((1, 1), (self.max_x - 2, self.max_y - 2)));
+    }
+
+    /// Sets every editable position in `region` to what `mark` gives for its current mark,
+    /// as a single undo step.
+    fn update_region(&mut self, region: ((u16, u16), (u16, u16)), mut mark: impl FnMut(bool) -> bool) {
+        let (top_left, bottom_right) = region;
+        for y in top_left.1..=bottom_right.1 {
+            for x in top_left.0..=bottom_right.0 {
+                if self.is_editable((x, y)) {
+                    let marked = mark(self.is_marked((x, y)));
+                    self.set_mark((x, y), marked);
+                }
+            }
+        }
+        self.history.commit();
+    }
+
+    /// Unmarks the selection, or the whole map.
+    pub fn clear_region(&mut self) {
+        self.update_region(self.edit_region(), |_| false);
+    }
+
+    /// Marks each position of the selection, or the whole map, with probability `density`.
+    pub fn fill_region(&mut self, random: &mut SeededRandom, density: f64) {
+        self.update_region(self.edit_region(), |_| random.chance(density));
+    }
+
+    /// Flips every mark in the selection, or the whole map.
+    pub fn invert_region(&mut self) {
+        self.update_region(self.edit_region(), |marked| !marked);
+    }
+
+    /// Stamps the preview with its top left corner on the cursor.
+    pub fn stamp_preview(&mut self) {
+        if let Some(preview) = self.preview.take() {
+            self.stamp(&preview, self.current_position);
+            self.preview = Some(preview);
+        }
+    }
+
+    /// Turns the preview over, and the clipboard with it when that's what's being pasted so
+    /// the next paste keeps the orientation.
+    pub fn transform_preview(&mut self, transform: Transform, includes_clipboard: bool) {
+        if let Some(preview) = &self.preview {
+            self.preview = Some(transform.apply(preview));
+        }
+        if includes_clipboard {
+            if let Some(clipboard) = &self.clipboard {
+                self.clipboard = Some(transform.apply(clipboard));
+            }
+        }
+    }
+
+    /// Whether the preview covers a position with a live cell.
+    pub fn is_previewed(&self, position: (u16, u16)) -> bool {
+        let Some(preview) = &self.preview else {
+            return false;
+        };
+        let (Some(x), Some(y)) = (
+            position.0.checked_sub(self.current_position.0),
+            position.1.checked_sub(self.current_position.1),
+        ) else {
+            return false;
+        };
+        return *preview.get(&(x, y)).unwrap_or(&false);
+    }
+
+    /// Stamps `pattern` so it sits in the middle of the map.
+    pub fn stamp_centered(&mut self, pattern: &Pattern) {
+        let width = pattern.keys().map(|position| position.0 + 1).max().unwrap_or(0);
+        let height = pattern.keys().map(|position| position.1 + 1).max().unwrap_or(0);
+        let origin = (
+            (self.max_x / 2).saturating_sub(width / 2).max(1),
+            (self.max_y / 2).saturating_sub(height / 2).max(1),
+        );
+        self.stamp(pattern, origin);
+    }
+
+    /// Every mark, along with the ones set aside while the map is shrunk to the terminal.
+    pub fn all_marks(&self) -> impl Iterator<Item = (&(u16, u16), &bool)> {
+        return self
+            .marked_positions
+            .iter()
+            .chain(self.clipped_marks.iter().map(|position| (position, &true)));
+    }
+
+    pub fn marked_cells(&self) -> Vec<(i64, i64)> {
+        return self
+            .marked_positions
+            .iter()
+            .filter(|(_, marked)| **marked)
+            .map(|(position, _)| (position.0 as i64, position.1 as i64))
+            .collect();
+    }
+
+    /// Switches to the next drawing tool, dropping a half drawn shape and lifting the pen.
+    pub fn next_tool(&mut self) {
+        self.tool = self.tool.next();
+        self.tool_anchor = None;
+        self.pen_down = false;
+        self.history.commit();
+    }
+
+    /// What `enter` does with the current tool: toggle a mark, put the pen down or lift it,
+    /// start or finish a shape, or flood fill from the cursor.
+    pub fn use_tool(&mut self) {
+        let cursor = self.current_position;
+        match self.tool {
+            DrawTool::Mark => self.toggle_mark(),
+            DrawTool::Pen => {
+                self.pen_down = !self.pen_down;
+                if self.pen_down {
+                    self.set_mark(cursor, true);
+                } else {
+                    self.history.commit();
+                }
+            }
+            DrawTool::FloodFill => self.flood_fill(),
+            tool => match self.tool_anchor.take() {
+                Some(anchor) => {
+                    for position in tool.shape(anchor, cursor).unwrap_or_default() {
+                        if self.is_editable(position) {
+                            self.set_mark(position, true);
+                        }
+                    }
+                    self.history.commit();
+                }
+                None => self.tool_anchor = Some(cursor),
+            },
+        }
+    }
+
+    /// Moves the cursor, marking the new position while the pen is down. A whole stroke is
+    /// one undo step, it's committed when the pen is lifted.
+    pub fn move_drawing(&mut self, move_direction: MoveDirections) {
+        self.move_chunk(move_direction);
+        if self.pen_down {
+            self.set_mark(self.current_position, true);
+        }
+    }
+
+    /// Moves the cursor to a clicked position and uses the tool there.
+    pub fn click(&mut self, position: (u16, u16)) {
+        if !self.is_editable(position) {
+            return;
+        }
+        self.current_position = position;
+        self.use_tool();
+    }
+
+    /// Paints the line from the cursor to a position dragged over, so quick drags leave no
+    /// gaps. The drag is one undo step once `end_drag` commits it.
+    pub fn drag_to(&mut self, position: (u16, u16)) {
+        if !self.is_editable(position) {
+            return;
+        }
+        for cell in line(self.current_position, position) {
+            self.set_mark(cell, true);
+        }
+        self.current_position = position;
+    }
+
+    pub fn end_drag(&mut self) {
+        self.history.commit();
+    }
+
+    /// The shape that `enter` would draw from the tool anchor to the cursor.
+    pub fn tool_shape(&self) -> Vec<(u16, u16)> {
+        return match self.tool_anchor {
+            Some(anchor) => self.tool.shape(anchor, self.current_position).unwrap_or_default(),
+            None => Vec::new(),
+        };
+    }
+
+    /// Flips every position connected to the cursor, up, down, left or right, that has the
+    /// same mark as the cursor. The border stops the fill.
+    pub fn flood_fill(&mut self) {
+        let start = self.current_position;
+        let target = self.is_marked(start);
+        let mut queue = VecDeque::from([start]);
+        while let Some(position) = queue.pop_front() {
+            if !self.is_editable(position) || self.is_marked(position) != target {
+                continue;
+            }
+            self.set_mark(position, !target);
+            let (x, y) = position;
+            queue.extend([(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]);
+        }
+        self.history.commit();
+    }
+}
+
+/// Density and seed for a random fill, typed into the prompt as `density [seed]`.
+#[derive(Debug, PartialEq)]
+pub struct FillSettings {
+    pub density: f64,
+    pub seed: u64,
+}
+
+impl FillSettings {
+    /// Reads `density [seed]`, an empty field falls back to the given default.
+    pub fn parse(input: &str, default_density: f64, default_seed: u64) -> Result<FillSettings, String> {
+        let mut fields = input.split_whitespace();
+        let density = match fields.next() {
+            Some(field) => match field.parse::<f64>() {
+                Ok(density) if (0.0..=1.0).contains(&density) => density,
+                _ => return Err(format!("Density must be between 0 and 1, got {}", field)),
+            },
+            None => default_density,
+        };
+        let seed = match fields.next() {
+            Some(field) => field
+                .parse::<u64>()
+                .map_err(|_| format!("Seed must be a whole number, got {}", field))?,
+            None => default_seed,
+        };
+        if let Some(extra) = fields.next() {
+            return Err(format!("Unexpected {}, expected density [seed]", extra));
+        }
+        return Ok(FillSettings { density, seed });
+    }
 }
@@ -1,4 +1,5 @@
-﻿use std::io::Stdout;
+﻿use std::collections::HashSet;
+use std::io::Stdout;
 use crossterm::execute;
 use crossterm::style::Stylize;
 use crossterm::style::Print;
@@ -11,39 +12,84 @@ use crossterm::{
 };
 use crate::design_world::DesignWorld;
 
+/// The key help printed under the position line, above the grid.
+const DESIGN_HELP: [&str; 15] = [
+    "e+ctrl: edit settings",
+    "c+ctrl: quit",
+    "p+ctrl: play",
+    "o+ctrl: load a pattern at the cursor",
+    "s+ctrl: save the design (.rle, .cells, .lif)",
+    "w+ctrl: save a snapshot",
+    "r+ctrl: restore a snapshot",
+    "l: browse the pattern library",
+    "v: start/end a selection, c: copy, x: cut, p: paste with a preview to rotate/mirror",
+    "e: erase, r: random fill, i: invert (the selection, or the whole map)",
+    "z: undo, y: redo",
+    "t: next drawing tool (mark, pen, line, rectangle, ellipse, flood fill)",
+    "w,a,s,d: move",
+    "enter: use the tool (mark a tile, pen down/up, start/finish a shape, flood fill)",
+    "mouse: click to use the tool, drag to paint",
+];
+
 pub struct DesignWorldDisplay {}
 
 impl DesignWorldDisplay {
+    /// Terminal rows above the grid, the position line and the help.
+    pub const HEADER_LINES: u16 = DESIGN_HELP.len() as u16 + 1;
+
+    /// The design map position under a terminal cell, if it's on the grid.
+    pub fn position_at(world: &DesignWorld, column: u16, row: u16) -> Option<(u16, u16)> {
+        let y = row.checked_sub(DesignWorldDisplay::HEADER_LINES)?;
+        if column >= world.max_x || y >= world.max_y {
+            return None;
+        }
+        return Some((column, y));
+    }
+
     pub fn print_design_world(stdout: &mut Stdout, world: &DesignWorld) {
         let chunk_address = world.current_position;
         execute!(stdout, Clear(ClearType::FromCursorUp), cursor::MoveTo(0, 0), Print("")).unwrap();
-        print!(
-                    "CurrentPosition: ({}, {})\n\
-                    e+ctrl: edit settings\n\
-                    c+ctrl: quit\n\
-                    p+ctrl: play\n\
-                    w,a,s,d: move\n\
-                    enter: mark a tile\n",
-                    world.current_position.0, world.current_position.1
+        println!(
+            "CurrentPosition: ({}, {}) :: Tool: {}{} :: Undo: {} :: Redo: {}",
+            world.current_position.0,
+            world.current_position.1,
+            world.tool.name(),
+            if world.pen_down { " (pen down)" } else { "" },
+            world.history.undo_len(),
+            world.history.redo_len()
         );
+        for line in DESIGN_HELP {
+            println!("{}", line);
+        }
+        let tool_shape: HashSet<(u16, u16)> = world.tool_shape().into_iter().collect();
         let x_check = world.max_x - 1;
         let y_check = world.max_y - 1;
         for y in 0..world.max_y {
             for x in 0..world.max_x {
                 let address = (x, y);
                 if address == chunk_address {
-                    print!("{}", format!("X").yellow());
+                    print!("{}", "X".yellow());
+                } else if world.is_previewed(address) {
+                    print!("{}", "O".green());
+                } else if tool_shape.contains(&address) {
+                    print!("{}", "o".cyan());
+                } else if world.is_selected(address) {
+                    if *world.marked_positions.get(&address).unwrap_or(&false) {
+                        print!("{}", "M".blue().on_dark_grey());
+                    } else {
+                        print!("{}", " ".on_dark_grey());
+                    }
                 } else if *world.marked_positions.get(&address).unwrap_or(&false) {
-                    print!("{}", format!("M").blue());
+                    print!("{}", "M".blue());
                 } else {
                     if y == 0 || x == 0 || x == x_check || y == y_check {
                         print!("+");
                     } else {
-                        print!("{}", " ");
+                        print!(" ");
                     }
                 }
             }
-            print!("\n");
+            println!();
         }
     }
 }
@@ -0,0 +1,149 @@
+/// What `enter` does in the editor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DrawTool {
+    /// Toggles the cell under the cursor.
+    #[default]
+    Mark,
+    /// Marks every cell the cursor moves over while the pen is down.
+    Pen,
+    Line,
+    Rectangle,
+    FilledRectangle,
+    Ellipse,
+    FilledEllipse,
+    /// Flips the connected area of cells that match the one under the cursor.
+    FloodFill,
+}
+
+impl DrawTool {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            DrawTool::Mark => "mark",
+            DrawTool::Pen => "pen",
+            DrawTool::Line => "line",
+            DrawTool::Rectangle => "rectangle",
+            DrawTool::FilledRectangle => "filled rectangle",
+            DrawTool::Ellipse => "ellipse",
+            DrawTool::FilledEllipse => "filled ellipse",
+            DrawTool::FloodFill => "flood fill",
+        };
+    }
+
+    pub fn next(&self) -> DrawTool {
+        return match self {
+            DrawTool::Mark => DrawTool::Pen,
+            DrawTool::Pen => DrawTool::Line,
+            DrawTool::Line => DrawTool::Rectangle,
+            DrawTool::Rectangle => DrawTool::FilledRectangle,
+            DrawTool::FilledRectangle => DrawTool::Ellipse,
+            DrawTool::Ellipse => DrawTool::FilledEllipse,
+            DrawTool::FilledEllipse => DrawTool::FloodFill,
+            DrawTool::FloodFill => DrawTool::Mark,
+        };
+    }
+
+    /// The cells of the shape from `start` to `end`, or `None` for tools that don't draw
+    /// between two corners.
+    pub fn shape(&self, start: (u16, u16), end: (u16, u16)) -> Option<Vec<(u16, u16)>> {
+        return match self {
+            DrawTool::Line => Some(line(start, end)),
+            DrawTool::Rectangle => Some(rectangle(start, end, false)),
+            DrawTool::FilledRectangle => Some(rectangle(start, end, true)),
+            DrawTool::Ellipse => Some(ellipse(start, end, false)),
+            DrawTool::FilledEllipse => Some(ellipse(start, end, true)),
+            DrawTool::Mark | DrawTool::Pen | DrawTool::FloodFill => None,
+        };
+    }
+}
+
+/// Bresenham's line from `start` to `end`, both ends included.
+pub fn line(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (mut x, mut y) = (start.0 as i32, start.1 as i32);
+    let (end_x, end_y) = (end.0 as i32, end.1 as i32);
+    let delta_x = (end_x - x).abs();
+    let delta_y = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = delta_x + delta_y;
+    let mut cells = Vec::new();
+    loop {
+        cells.push((x as u16, y as u16));
+        if x == end_x && y == end_y {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= delta_y {
+            error += delta_y;
+            x += step_x;
+        }
+        if doubled <= delta_x {
+            error += delta_x;
+            y += step_y;
+        }
+    }
+    return cells;
+}
+
+/// The rectangle with opposite corners `start` and `end`, either its outline or all of it.
+pub fn rectangle(start: (u16, u16), end: (u16, u16), filled: bool) -> Vec<(u16, u16)> {
+    let (left, right) = (start.0.min(end.0), start.0.max(end.0));
+    let (top, bottom) = (start.1.min(end.1), start.1.max(end.1));
+    let mut cells = Vec::new();
+    for y in top..=bottom {
+        for x in left..=right {
+            if filled || x == left || x == right || y == top || y == bottom {
+                cells.push((x, y));
+            }
+        }
+    }
+    return cells;
+}
+
+/// The ellipse inscribed in the rectangle with opposite corners `start` and `end`. The
+/// outline takes the ends of every row and every column so steep edges have no gaps, a
+/// filled ellipse is its outline with each row filled in.
+pub fn ellipse(start: (u16, u16), end: (u16, u16), filled: bool) -> Vec<(u16, u16)> {
+    let (left, right) = (start.0.min(end.0), start.0.max(end.0));
+    let (top, bottom) = (start.1.min(end.1), start.1.max(end.1));
+    let mut cells = Vec::new();
+    for y in top..=bottom {
+        let (first, last) = ellipse_span(left, right, top, bottom, y);
+        cells.push((first, y));
+        cells.push((last, y));
+    }
+    for x in left..=right {
+        let (first, last) = ellipse_span(top, bottom, left, right, x);
+        cells.push((x, first));
+        cells.push((x, last));
+    }
+    if filled {
+        let outline = cells.clone();
+        for y in top..=bottom {
+            let row = outline.iter().filter(|cell| cell.1 == y).map(|cell| cell.0);
+            let first = row.clone().min().unwrap_or(left);
+            let last = row.max().unwrap_or(left);
+            cells.extend((first..=last).map(|x| (x, y)));
+        }
+    }
+    cells.sort();
+    cells.dedup();
+    return cells;
+}
+
+/// The first and last position along one axis that the ellipse covers at `across` on the
+/// other axis.
+fn ellipse_span(low: u16, high: u16, across_low: u16, across_high: u16, across: u16) -> (u16, u16) {
+    let center = (low as f64 + high as f64) / 2.0;
+    let radius = (high as f64 - low as f64) / 2.0;
+    let across_center = (across_low as f64 + across_high as f64) / 2.0;
+    let across_radius = (across_high as f64 - across_low as f64) / 2.0;
+    let offset = if across_radius > 0.0 {
+        ((across as f64 - across_center) / across_radius).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+    let reach = radius * (1.0 - offset * offset).sqrt();
+    let first = (center - reach + 0.5).floor() as u16;
+    let last = (center + reach - 0.5).ceil() as u16;
+    return (first.min(last), first.max(last));
+}
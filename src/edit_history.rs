@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+/// One design map position changing from `before` to `after`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellChange {
+    pub position: (u16, u16),
+    pub before: bool,
+    pub after: bool,
+}
+
+/// Undo and redo stacks of editor operations, each stored as the cells it changed rather
+/// than a copy of the map. The oldest operations are forgotten once either `max_edits`
+/// operations or `max_changes` changed cells are held.
+pub struct EditHistory {
+    undo_stack: VecDeque<Vec<CellChange>>,
+    redo_stack: Vec<Vec<CellChange>>,
+    pending: Vec<CellChange>,
+    held_changes: usize,
+    max_edits: usize,
+    max_changes: usize,
+}
+
+impl EditHistory {
+    pub fn new(max_edits: usize, max_changes: usize) -> EditHistory {
+        return EditHistory {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            pending: Vec::new(),
+            held_changes: 0,
+            max_edits,
+            max_changes,
+        };
+    }
+
+    /// Adds a change to the operation in progress.
+    pub fn record(&mut self, change: CellChange) {
+        self.pending.push(change);
+    }
+
+    /// Closes the operation in progress as one undo step, a new edit drops anything that
+    /// could have been redone.
+    pub fn commit(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let edit = std::mem::take(&mut self.pending);
+        for dropped in self.redo_stack.drain(..) {
+            self.held_changes -= dropped.len();
+        }
+        self.held_changes += edit.len();
+        self.undo_stack.push_back(edit);
+        while self.undo_stack.len() > self.max_edits || self.held_changes > self.max_changes {
+            match self.undo_stack.pop_front() {
+                Some(dropped) => self.held_changes -= dropped.len(),
+                None => break,
+            }
+        }
+    }
+
+    /// The last operation, moved onto the redo stack. The caller puts the `before` states back.
+    pub fn undo(&mut self) -> Option<&Vec<CellChange>> {
+        let edit = self.undo_stack.pop_back()?;
+        self.redo_stack.push(edit);
+        return self.redo_stack.last();
+    }
+
+    /// The last undone operation, moved back onto the undo stack. The caller reapplies the
+    /// `after` states.
+    pub fn redo(&mut self) -> Option<&Vec<CellChange>> {
+        let edit = self.redo_stack.pop()?;
+        self.undo_stack.push_back(edit);
+        return self.undo_stack.back();
+    }
+
+    pub fn undo_len(&self) -> usize {
+        return self.undo_stack.len();
+    }
+
+    pub fn redo_len(&self) -> usize {
+        return self.redo_stack.len();
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::{self, Write};
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+
+/// Unchanged cells shorter than this between two changes are reprinted rather than skipped
+/// with a cursor move, which would take more bytes than the cells themselves.
+const MAX_BRIDGED_GAP: usize = 6;
+
+/// One character on screen and its colour, `None` being the terminal's default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameCell {
+    pub glyph: char,
+    pub color: Option<Color>,
+}
+
+impl FrameCell {
+    pub const BLANK: FrameCell = FrameCell { glyph: ' ', color: None };
+}
+
+/// A whole screen of cells, built up each frame before anything is written out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameBuffer {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<FrameCell>,
+}
+
+impl FrameBuffer {
+    pub fn new(width: usize, height: usize) -> FrameBuffer {
+        return FrameBuffer { width, height, cells: vec![FrameCell::BLANK; width * height] };
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> FrameCell {
+        return self.cells[y * self.width + x];
+    }
+
+    /// Sets a cell, anything off the buffer is dropped.
+    pub fn set(&mut self, x: usize, y: usize, cell: FrameCell) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell;
+        }
+    }
+
+    /// Writes uncoloured text from the start of a row, cut off at the buffer's width.
+    pub fn put_text(&mut self, y: usize, text: &str) {
+        for (x, glyph) in text.chars().enumerate() {
+            self.set(x, y, FrameCell { glyph, color: None });
+        }
+    }
+}
+
+/// Draws frames by comparing them with the last one and only writing the cells that
+/// changed, with a cursor move at the start of each run and a colour change only where
+/// the colour does. The frame goes out in one write and flush.
+pub struct DiffRenderer {
+    previous: Option<FrameBuffer>,
+}
+
+impl DiffRenderer {
+    pub fn new() -> DiffRenderer {
+        return DiffRenderer { previous: None };
+    }
+
+    /// Forgets the last frame, for when something else has drawn over the screen.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn render<W: Write>(&mut self, out: &mut W, frame: FrameBuffer) -> io::Result<()> {
+        let bytes = self.diff(&frame)?;
+        out.write_all(&bytes)?;
+        out.flush()?;
+        self.previous = Some(frame);
+        return Ok(());
+    }
+
+    /// The escape sequences that turn the last frame into `frame`, everything when there's
+    /// no last frame or its size differs.
+    pub fn diff(&self, frame: &FrameBuffer) -> io::Result<Vec<u8>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let previous = self
+            .previous
+            .as_ref()
+            .filter(|previous| previous.width == frame.width && previous.height == frame.height);
+        if previous.is_none() {
+            queue!(bytes, ResetColor, Clear(ClearType::All))?;
+        }
+        let changed = |x: usize, y: usize| match previous {
+            Some(previous) => previous.get(x, y) != frame.get(x, y),
+            None => frame.get(x, y) != FrameCell::BLANK,
+        };
+
+        let mut color: Option<Color> = None;
+        for y in 0..frame.height {
+            let mut x = 0;
+            while x < frame.width {
+                if !changed(x, y) {
+                    x += 1;
+                    continue;
+                }
+                queue!(bytes, MoveTo(x as u16, y as u16))?;
+                let mut run_end = x;
+                let mut gap = 0;
+                let mut next = x;
+                while next < frame.width && gap <= MAX_BRIDGED_GAP {
+                    if changed(next, y) {
+                        run_end = next;
+                        gap = 0;
+                    } else {
+                        gap += 1;
+                    }
+                    next += 1;
+                }
+                for run_x in x..=run_end {
+                    let cell = frame.get(run_x, y);
+                    if cell.color != color {
+                        match cell.color {
+                            Some(new_color) => queue!(bytes, SetForegroundColor(new_color))?,
+                            None => queue!(bytes, ResetColor)?,
+                        }
+                        color = cell.color;
+                    }
+                    queue!(bytes, Print(cell.glyph))?;
+                }
+                x = run_end + 1;
+            }
+        }
+        if color.is_some() {
+            queue!(bytes, ResetColor)?;
+        }
+        return Ok(bytes);
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use crate::rule::Rule;
+use crate::world::World;
+
+const DEAD_LEAF: usize = 0;
+const LIVE_LEAF: usize = 1;
+const SMALLEST_ROOT_LEVEL: u32 = 3;
+const DEFAULT_MAX_NODES: usize = 4_000_000;
+/// Largest `step_log` `advance_pow2` takes, the root has to grow to level `step_log + 3`
+/// and past that its `i64` origin and size overflow.
+pub const MAX_STEP_LOG: u32 = 60;
+/// Most generations `advance` takes, every power of two it splits them into stays within
+/// `MAX_STEP_LOG`.
+pub const MAX_GENERATIONS: u128 = (2 << MAX_STEP_LOG) - 1;
+
+/// A square quadtree node covering `2^level` x `2^level` cells, level 0 nodes are single cells.
+struct Node {
+    level: u32,
+    nw: usize,
+    ne: usize,
+    sw: usize,
+    se: usize,
+    population: u64,
+}
+
+/// Gosper's HashLife: identical quadtree nodes are shared and the result of advancing each
+/// one is memoized, so repetitive patterns can jump `2^k` generations in a single step.
+///
+/// The plane is unbounded, so the results match a `World` using `StorageKind::Unbounded`.
+pub struct HashLife {
+    nodes: Vec<Node>,
+    node_ids: HashMap<(usize, usize, usize, usize), usize>,
+    empty_nodes: Vec<usize>,
+    results: HashMap<(usize, u32), usize>,
+    root: usize,
+    origin: (i64, i64),
+    rule: Rule,
+    max_nodes: usize,
+    pub generation: u128,
+}
+
+impl HashLife {
+    pub fn new(rule: Rule) -> HashLife {
+        let mut hashlife = HashLife {
+            nodes: Vec::new(),
+            node_ids: HashMap::new(),
+            empty_nodes: Vec::new(),
+            results: HashMap::new(),
+            root: DEAD_LEAF,
+            origin: (0, 0),
+            rule,
+            max_nodes: DEFAULT_MAX_NODES,
+            generation: 0,
+        };
+        hashlife.reset_nodes();
+        hashlife.root = hashlife.empty_node(SMALLEST_ROOT_LEVEL);
+        let half = 1_i64 << (SMALLEST_ROOT_LEVEL - 1);
+        hashlife.origin = (-half, -half);
+        return hashlife;
+    }
+
+    pub fn from_cells<I: IntoIterator<Item = (i64, i64)>>(cells: I, rule: Rule) -> HashLife {
+        let mut hashlife = HashLife::new(rule);
+        for key in cells {
+            hashlife.insert_position(key);
+        }
+        return hashlife;
+    }
+
+    pub fn from_world(world: &World) -> HashLife {
+        let mut hashlife = HashLife::from_cells(world.live_cells(), world.rule);
+        hashlife.generation = world.current_generation;
+        return hashlife;
+    }
+
+    /// Whether this still holds `world`'s cells, so a jump can carry on from it and reuse
+    /// every step it has memoized.
+    pub fn is_in_step_with(&self, world: &World) -> bool {
+        return self.rule == world.rule && self.generation == world.current_generation;
+    }
+
+    /// Replaces the live cells of `world` with this pattern, cells that fall outside a
+    /// bounded world are dropped.
+    pub fn write_to_world(&self, world: &mut World) {
+        world.reset_world();
+        for key in self.live_cells() {
+            world.insert_live_cell(key);
+        }
+        world.current_generation = self.generation;
+    }
+
+    pub fn insert_position(&mut self, key: (i64, i64)) {
+        while !self.contains(key) {
+            self.expand();
+        }
+        let level = self.nodes[self.root].level;
+        let x = (key.0 - self.origin.0) as u64;
+        let y = (key.1 - self.origin.1) as u64;
+        self.root = self.set_cell(self.root, level, x, y);
+    }
+
+    pub fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        self.collect_live_cells(self.root, self.origin, &mut cells);
+        return cells;
+    }
+
+    /// Advances the pattern by exactly `generations`, taking the largest power of two
+    /// jumps available.
+    pub fn advance(&mut self, generations: u128) {
+        let mut remaining = generations;
+        let mut step_log = 0;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                self.advance_pow2(step_log);
+            }
+            remaining >>= 1;
+            step_log += 1;
+        }
+    }
+
+    /// Advances the pattern by `2^step_log` generations in one memoized step.
+    pub fn advance_pow2(&mut self, step_log: u32) {
+        // The result of a level k node is its centre after 2^(k - 2) generations, so grow
+        // the root until nothing live sits outside its centre and there is room to step.
+        while self.nodes[self.root].level < step_log + 2 || !self.is_padded(self.root) {
+            self.expand();
+        }
+        self.expand();
+
+        let level = self.nodes[self.root].level;
+        let quarter = 1_i64 << (level - 2);
+        self.root = self.step(self.root, step_log);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        self.generation += 1_u128 << step_log;
+
+        if self.nodes.len() > self.max_nodes {
+            self.collect_garbage();
+        }
+    }
+
+    fn reset_nodes(&mut self) {
+        self.nodes.clear();
+        self.node_ids.clear();
+        self.empty_nodes.clear();
+        self.results.clear();
+        self.nodes.push(Node { level: 0, nw: 0, ne: 0, sw: 0, se: 0, population: 0 });
+        self.nodes.push(Node { level: 0, nw: 0, ne: 0, sw: 0, se: 0, population: 1 });
+        self.empty_nodes.push(DEAD_LEAF);
+    }
+
+    fn join(&mut self, nw: usize, ne: usize, sw: usize, se: usize) -> usize {
+        if let Some(id) = self.node_ids.get(&(nw, ne, sw, se)) {
+            return *id;
+        }
+        let population = self.nodes[nw].population
+            + self.nodes[ne].population
+            + self.nodes[sw].population
+            + self.nodes[se].population;
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            level: self.nodes[nw].level + 1,
+            nw,
+            ne,
+            sw,
+            se,
+            population,
+        });
+        self.node_ids.insert((nw, ne, sw, se), id);
+        return id;
+    }
+
+    fn empty_node(&mut self, level: u32) -> usize {
+        while self.empty_nodes.len() <= level as usize {
+            let child = *self.empty_nodes.last().unwrap();
+            let node = self.join(child, child, child, child);
+            self.empty_nodes.push(node);
+        }
+        return self.empty_nodes[level as usize];
+    }
+
+    fn contains(&self, key: (i64, i64)) -> bool {
+        let size = 1_i64 << self.nodes[self.root].level;
+        return key.0 >= self.origin.0
+            && key.1 >= self.origin.1
+            && key.0 < self.origin.0 + size
+            && key.1 < self.origin.1 + size;
+    }
+
+    /// Doubles the root around its current centre.
+    fn expand(&mut self) {
+        let root = &self.nodes[self.root];
+        let (level, nw, ne, sw, se) = (root.level, root.nw, root.ne, root.sw, root.se);
+        let empty = self.empty_node(level - 1);
+        let new_nw = self.join(empty, empty, empty, nw);
+        let new_ne = self.join(empty, empty, ne, empty);
+        let new_sw = self.join(empty, sw, empty, empty);
+        let new_se = self.join(se, empty, empty, empty);
+        self.root = self.join(new_nw, new_ne, new_sw, new_se);
+
+        let half = 1_i64 << (level - 1);
+        self.origin = (self.origin.0 - half, self.origin.1 - half);
+    }
+
+    /// Whether every live cell sits in the centre half of `node`.
+    fn is_padded(&self, node: usize) -> bool {
+        let node = &self.nodes[node];
+        let nw = &self.nodes[node.nw];
+        let ne = &self.nodes[node.ne];
+        let sw = &self.nodes[node.sw];
+        let se = &self.nodes[node.se];
+        return nw.population == self.nodes[nw.se].population
+            && ne.population == self.nodes[ne.sw].population
+            && sw.population == self.nodes[sw.ne].population
+            && se.population == self.nodes[se.nw].population;
+    }
+
+    fn set_cell(&mut self, node: usize, level: u32, x: u64, y: u64) -> usize {
+        if level == 0 {
+            return LIVE_LEAF;
+        }
+        let half = 1_u64 << (level - 1);
+        let n = &self.nodes[node];
+        let (mut nw, mut ne, mut sw, mut se) = (n.nw, n.ne, n.sw, n.se);
+        match (x >= half, y >= half) {
+            (false, false) => nw = self.set_cell(nw, level - 1, x, y),
+            (true, false) => ne = self.set_cell(ne, level - 1, x - half, y),
+            (false, true) => sw = self.set_cell(sw, level - 1, x, y - half),
+            (true, true) => se = self.set_cell(se, level - 1, x - half, y - half),
+        }
+        return self.join(nw, ne, sw, se);
+    }
+
+    fn collect_live_cells(&self, node: usize, origin: (i64, i64), cells: &mut Vec<(i64, i64)>) {
+        let n = &self.nodes[node];
+        if n.population == 0 {
+            return;
+        }
+        if n.level == 0 {
+            cells.push(origin);
+            return;
+        }
+        let half = 1_i64 << (n.level - 1);
+        self.collect_live_cells(n.nw, origin, cells);
+        self.collect_live_cells(n.ne, (origin.0 + half, origin.1), cells);
+        self.collect_live_cells(n.sw, (origin.0, origin.1 + half), cells);
+        self.collect_live_cells(n.se, (origin.0 + half, origin.1 + half), cells);
+    }
+
+    /// The level `k - 1` node in the middle of a level `k` node.
+    fn centre(&mut self, node: usize) -> usize {
+        let n = &self.nodes[node];
+        let (nw, ne, sw, se) = (
+            self.nodes[n.nw].se,
+            self.nodes[n.ne].sw,
+            self.nodes[n.sw].ne,
+            self.nodes[n.se].nw,
+        );
+        return self.join(nw, ne, sw, se);
+    }
+
+    fn horizontal_middle(&mut self, west: usize, east: usize) -> usize {
+        let (w, e) = (&self.nodes[west], &self.nodes[east]);
+        let (nw, ne, sw, se) = (w.ne, e.nw, w.se, e.sw);
+        return self.join(nw, ne, sw, se);
+    }
+
+    fn vertical_middle(&mut self, north: usize, south: usize) -> usize {
+        let (n, s) = (&self.nodes[north], &self.nodes[south]);
+        let (nw, ne, sw, se) = (n.sw, n.se, s.nw, s.ne);
+        return self.join(nw, ne, sw, se);
+    }
+
+    /// The centre of a level `k` node after `2^step_log` generations, `step_log <= k - 2`.
+    fn step(&mut self, node: usize, step_log: u32) -> usize {
+        let level = self.nodes[node].level;
+        if self.nodes[node].population == 0 {
+            return self.empty_node(level - 1);
+        }
+        if let Some(result) = self.results.get(&(node, step_log)) {
+            return *result;
+        }
+
+        let result = if level == 2 {
+            self.step_level_two(node)
+        } else {
+            let n = &self.nodes[node];
+            let (nw, ne, sw, se) = (n.nw, n.ne, n.sw, n.se);
+            let north = self.horizontal_middle(nw, ne);
+            let west = self.vertical_middle(nw, sw);
+            let middle = self.centre(node);
+            let east = self.vertical_middle(ne, se);
+            let south = self.horizontal_middle(sw, se);
+            let nine = [nw, north, ne, west, middle, east, sw, south, se];
+
+            // At full speed both halves of the jump advance 2^(k - 3) generations,
+            // slower jumps skip the first half and just take the centres.
+            let is_full_speed = step_log == level - 2;
+            let mut partial = [0; 9];
+            for (index, sub_node) in nine.iter().enumerate() {
+                partial[index] = if is_full_speed {
+                    self.step(*sub_node, level - 3)
+                } else {
+                    self.centre(*sub_node)
+                };
+            }
+
+            let inner_step_log = if is_full_speed { level - 3 } else { step_log };
+            let quadrants = [
+                self.join(partial[0], partial[1], partial[3], partial[4]),
+                self.join(partial[1], partial[2], partial[4], partial[5]),
+                self.join(partial[3], partial[4], partial[6], partial[7]),
+                self.join(partial[4], partial[5], partial[7], partial[8]),
+            ];
+            let mut stepped = [0; 4];
+            for (index, quadrant) in quadrants.iter().enumerate() {
+                stepped[index] = self.step(*quadrant, inner_step_log);
+            }
+            self.join(stepped[0], stepped[1], stepped[2], stepped[3])
+        };
+
+        self.results.insert((node, step_log), result);
+        return result;
+    }
+
+    /// Brute forces one generation for the middle 2x2 cells of a 4x4 node.
+    fn step_level_two(&mut self, node: usize) -> usize {
+        let mut cells = [[false; 4]; 4];
+        let n = &self.nodes[node];
+        for (quadrant, (x_offset, y_offset)) in [(n.nw, (0, 0)), (n.ne, (2, 0)), (n.sw, (0, 2)), (n.se, (2, 2))] {
+            let q = &self.nodes[quadrant];
+            cells[y_offset][x_offset] = q.nw == LIVE_LEAF;
+            cells[y_offset][x_offset + 1] = q.ne == LIVE_LEAF;
+            cells[y_offset + 1][x_offset] = q.sw == LIVE_LEAF;
+            cells[y_offset + 1][x_offset + 1] = q.se == LIVE_LEAF;
+        }
+
+        let mut next = [DEAD_LEAF; 4];
+        for (index, (x, y)) in [(1, 1), (2, 1), (1, 2), (2, 2)].iter().enumerate() {
+            let mut live_neighbors = 0;
+            for row in &cells[(y - 1)..=(y + 1)] {
+                live_neighbors += row[(x - 1)..=(x + 1)].iter().filter(|alive| **alive).count();
+            }
+            if cells[*y][*x] {
+                live_neighbors -= 1;
+            }
+            let alive = if cells[*y][*x] {
+                self.rule.survives(live_neighbors)
+            } else {
+                self.rule.is_born(live_neighbors)
+            };
+            if alive {
+                next[index] = LIVE_LEAF;
+            }
+        }
+        return self.join(next[0], next[1], next[2], next[3]);
+    }
+
+    /// Drops every node and memoized result that is no longer reachable from the root.
+    fn collect_garbage(&mut self) {
+        let old_nodes = std::mem::take(&mut self.nodes);
+        self.reset_nodes();
+        let mut copied: HashMap<usize, usize> = HashMap::new();
+        copied.insert(DEAD_LEAF, DEAD_LEAF);
+        copied.insert(LIVE_LEAF, LIVE_LEAF);
+        self.root = self.copy_node(&old_nodes, self.root, &mut copied);
+    }
+
+    fn copy_node(&mut self, old_nodes: &[Node], node: usize, copied: &mut HashMap<usize, usize>) -> usize {
+        if let Some(id) = copied.get(&node) {
+            return *id;
+        }
+        let n = &old_nodes[node];
+        let nw = self.copy_node(old_nodes, n.nw, copied);
+        let ne = self.copy_node(old_nodes, n.ne, copied);
+        let sw = self.copy_node(old_nodes, n.sw, copied);
+        let se = self.copy_node(old_nodes, n.se, copied);
+        let id = self.join(nw, ne, sw, se);
+        copied.insert(node, id);
+        return id;
+    }
+}
@@ -0,0 +1,106 @@
+use crate::pattern_file::{live_cells, normalize, pattern_size, Pattern, PatternError, PatternInfo};
+use crate::rule::Rule;
+
+/// Reads Life 1.06, a `#Life 1.06` header followed by one `x y` live cell per line.
+/// Other `#` lines are kept as comments.
+pub fn read_life_106(contents: &str) -> Result<(Pattern, PatternInfo), PatternError> {
+    let mut info = PatternInfo::default();
+    let mut cells = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#Life") {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            info.comments.push(comment.trim().to_string());
+            continue;
+        }
+        let parse_error = |message: String| PatternError::Parse { line: index + 1, message };
+        let mut parts = line.split_whitespace();
+        let (Some(x), Some(y), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(parse_error(format!("expected 'x y', found '{}'", line)));
+        };
+        let x = x.parse::<i64>().map_err(|_| parse_error(format!("'{}' is not a coordinate", x)))?;
+        let y = y.parse::<i64>().map_err(|_| parse_error(format!("'{}' is not a coordinate", y)))?;
+        cells.push((x, y));
+    }
+    return Ok((normalize(cells)?, info));
+}
+
+pub fn write_life_106(pattern: &Pattern, _info: &PatternInfo) -> String {
+    let mut contents = String::from("#Life 1.06\n");
+    for (x, y) in live_cells(pattern) {
+        contents.push_str(&format!("{} {}\n", x, y));
+    }
+    return contents;
+}
+
+/// Reads Life 1.05: `#D` description lines, `#N` for Conway's rule or `#R` with another,
+/// then `#P x y` blocks of `.` and `*` rows placed at their offsets.
+pub fn read_life_105(contents: &str) -> Result<(Pattern, PatternInfo), PatternError> {
+    let mut info = PatternInfo::default();
+    let mut cells = Vec::new();
+    let mut block_origin = (0_i64, 0_i64);
+    let mut row = 0;
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let parse_error = |message: String| PatternError::Parse { line: index + 1, message };
+        if line.is_empty() || line.starts_with("#Life") {
+            continue;
+        }
+        if let Some(description) = line.strip_prefix("#D") {
+            info.comments.push(description.trim().to_string());
+        } else if line.starts_with("#N") {
+            info.rule = Some(Rule::conway());
+        } else if let Some(rulestring) = line.strip_prefix("#R") {
+            let rule = Rule::parse(rulestring).map_err(|error| parse_error(error.to_string()))?;
+            info.rule = Some(rule);
+        } else if let Some(offset) = line.strip_prefix("#P") {
+            let mut parts = offset.split_whitespace().map(|part| part.parse::<i64>());
+            let (Some(Ok(x)), Some(Ok(y)), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(parse_error(format!("expected '#P x y', found '{}'", line)));
+            };
+            block_origin = (x, y);
+            row = 0;
+        } else if line.starts_with('#') {
+            // Unknown `#` lines are skipped like other readers do.
+        } else {
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '*' => cells.push((block_origin.0 + x as i64, block_origin.1 + row)),
+                    '.' => {}
+                    _ => return Err(parse_error(format!("unexpected '{}' in a cell row", c))),
+                }
+            }
+            row += 1;
+        }
+    }
+    return Ok((normalize(cells)?, info));
+}
+
+/// Writes Life 1.05 as a single `#P 0 0` block.
+pub fn write_life_105(pattern: &Pattern, info: &PatternInfo) -> String {
+    let mut contents = String::from("#Life 1.05\n");
+    for comment in info.name.iter().chain(info.comments.iter()) {
+        contents.push_str(&format!("#D {}\n", comment));
+    }
+    match info.rule {
+        Some(rule) if rule != Rule::conway() => contents.push_str(&format!("#R {}\n", rule)),
+        _ => contents.push_str("#N\n"),
+    }
+    contents.push_str("#P 0 0\n");
+
+    let (width, height) = pattern_size(pattern);
+    for y in 0..height {
+        let row: String = (0..width)
+            .map(|x| {
+                let alive = *pattern.get(&(x as u16, y as u16)).unwrap_or(&false);
+                if alive { '*' } else { '.' }
+            })
+            .collect();
+        let trimmed = row.trim_end_matches('.');
+        contents.push_str(if trimmed.is_empty() { "." } else { trimmed });
+        contents.push('\n');
+    }
+    return contents;
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use crate::pattern_file::{PatternError, PatternInfo};
+use crate::rule::Rule;
+
+/// Leaves are 8x8 blocks of cells, the smallest node Golly writes for two-state rules.
+const LEAF_LEVEL: u32 = 3;
+const LEAF_SIZE: i128 = 1 << LEAF_LEVEL;
+
+enum MacrocellNode {
+    Leaf(Vec<(u8, u8)>),
+    Branch { level: u32, children: [usize; 4] },
+}
+
+/// Reads Golly's Macrocell format: a `[M2]` line, `#R` rule and `#G` generation lines,
+/// then one quadtree node per line. Leaf lines draw an 8x8 block with `.`, `*` and `$`,
+/// every other line is `level nw ne sw se` naming earlier lines from 1, or 0 for empty.
+///
+/// The last node is the root, centred on `(0, 0)` like Golly places it, so the cells come
+/// back at exactly the coordinates they were saved from.
+pub fn read_macrocell(contents: &str) -> Result<(Vec<(i64, i64)>, PatternInfo), PatternError> {
+    let mut info = PatternInfo::default();
+    let mut nodes: Vec<MacrocellNode> = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let parse_error = |message: String| PatternError::Parse { line: index + 1, message };
+        if line.is_empty() || line.starts_with("[M2]") {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut chars = comment.chars();
+            let kind = chars.next();
+            let text = chars.as_str().trim();
+            match kind {
+                Some('R') => info.rule = Some(Rule::parse(text).map_err(|error| parse_error(error.to_string()))?),
+                Some('G') => {
+                    let generation = text
+                        .parse::<u128>()
+                        .map_err(|_| parse_error(format!("'{}' is not a generation", text)))?;
+                    info.generation = Some(generation);
+                }
+                Some('N') => info.name = Some(text.to_string()),
+                Some('C') => info.comments.push(text.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+
+        let node = if line.starts_with(['.', '*', '$']) {
+            read_leaf(line).map_err(parse_error)?
+        } else {
+            read_branch(line, &nodes).map_err(parse_error)?
+        };
+        nodes.push(node);
+    }
+
+    let mut cells = Vec::new();
+    if let Some(root) = nodes.last() {
+        let half = 1_i128 << (node_level(root) - 1);
+        expand(&nodes, nodes.len(), -half, -half, &mut cells)?;
+    }
+    return Ok((cells, info));
+}
+
+fn read_leaf(line: &str) -> Result<MacrocellNode, String> {
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0_u8, 0_u8);
+    for c in line.chars() {
+        // Each row may end with a `$`, so y reaches the leaf size only after the last one.
+        let is_full = match c {
+            '$' => y as i128 >= LEAF_SIZE,
+            _ => x as i128 >= LEAF_SIZE || y as i128 >= LEAF_SIZE,
+        };
+        if is_full {
+            return Err("leaf is larger than 8x8".to_string());
+        }
+        match c {
+            '$' => {
+                x = 0;
+                y += 1;
+                continue;
+            }
+            '*' => cells.push((x, y)),
+            '.' => {}
+            _ => return Err(format!("unexpected '{}' in a leaf", c)),
+        }
+        x += 1;
+    }
+    return Ok(MacrocellNode::Leaf(cells));
+}
+
+fn read_branch(line: &str, nodes: &[MacrocellNode]) -> Result<MacrocellNode, String> {
+    let numbers = line
+        .split_whitespace()
+        .map(|part| part.parse::<usize>().map_err(|_| format!("'{}' is not a node number", part)))
+        .collect::<Result<Vec<usize>, String>>()?;
+    let [level, nw, ne, sw, se] = numbers[..] else {
+        return Err(format!("expected 'level nw ne sw se', found '{}'", line));
+    };
+    let level = level as u32;
+    if level <= LEAF_LEVEL {
+        return Err(format!("level {} nodes are only used by multi-state rules", level));
+    }
+    if level > 64 {
+        return Err(format!("level {} is too large", level));
+    }
+    let children = [nw, ne, sw, se];
+    for child in children {
+        if child > nodes.len() {
+            return Err(format!("node {} hasn't been defined yet", child));
+        }
+        if child > 0 && node_level(&nodes[child - 1]) != level - 1 {
+            return Err(format!("node {} isn't a level {} node", child, level - 1));
+        }
+    }
+    return Ok(MacrocellNode::Branch { level, children });
+}
+
+fn node_level(node: &MacrocellNode) -> u32 {
+    return match node {
+        MacrocellNode::Leaf(_) => LEAF_LEVEL,
+        MacrocellNode::Branch { level, .. } => *level,
+    };
+}
+
+/// Collects the live cells of node `id` with its top left corner at `(x, y)`.
+fn expand(
+    nodes: &[MacrocellNode],
+    id: usize,
+    x: i128,
+    y: i128,
+    cells: &mut Vec<(i64, i64)>,
+) -> Result<(), PatternError> {
+    if id == 0 {
+        return Ok(());
+    }
+    match &nodes[id - 1] {
+        MacrocellNode::Leaf(leaf_cells) => {
+            for (cell_x, cell_y) in leaf_cells {
+                let cell = (x + *cell_x as i128, y + *cell_y as i128);
+                let cell = (
+                    i64::try_from(cell.0).map_err(|_| PatternError::TooLarge)?,
+                    i64::try_from(cell.1).map_err(|_| PatternError::TooLarge)?,
+                );
+                cells.push(cell);
+            }
+        }
+        MacrocellNode::Branch { level, children } => {
+            let half = 1_i128 << (level - 1);
+            let offsets = [(0, 0), (half, 0), (0, half), (half, half)];
+            for (child, (x_offset, y_offset)) in children.iter().zip(offsets) {
+                expand(nodes, *child, x + x_offset, y + y_offset, cells)?;
+            }
+        }
+    }
+    return Ok(());
+}
+
+/// Writes live cells as Macrocell with a root centred on `(0, 0)`, identical subtrees are
+/// written once and shared, which is what keeps huge regular patterns small.
+pub fn write_macrocell(cells: &[(i64, i64)], info: &PatternInfo) -> String {
+    let mut contents = String::from("[M2]\n");
+    contents.push_str(&format!("#R {}\n", info.rule.unwrap_or_default()));
+    if let Some(generation) = info.generation {
+        contents.push_str(&format!("#G {}\n", generation));
+    }
+    if let Some(name) = &info.name {
+        contents.push_str(&format!("#N {}\n", name));
+    }
+    for comment in info.comments.iter() {
+        contents.push_str(&format!("#C {}\n", comment));
+    }
+    if cells.is_empty() {
+        return contents;
+    }
+
+    let fits = |level: u32| {
+        let half = 1_i128 << (level - 1);
+        return cells.iter().all(|(x, y)| {
+            (-half..half).contains(&(*x as i128)) && (-half..half).contains(&(*y as i128))
+        });
+    };
+    let mut level = LEAF_LEVEL;
+    while !fits(level) {
+        level += 1;
+    }
+
+    let half = 1_i128 << (level - 1);
+    let cells: Vec<(i128, i128)> = cells.iter().map(|(x, y)| (*x as i128, *y as i128)).collect();
+    let mut node_ids: HashMap<String, usize> = HashMap::new();
+    let mut lines: Vec<String> = Vec::new();
+    build_node(cells, level, -half, -half, &mut node_ids, &mut lines);
+    for line in lines {
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+    return contents;
+}
+
+/// Writes the node covering the `2^level` square at `(x, y)` and everything below it,
+/// returning its line number or 0 when it's empty.
+fn build_node(
+    cells: Vec<(i128, i128)>,
+    level: u32,
+    x: i128,
+    y: i128,
+    node_ids: &mut HashMap<String, usize>,
+    lines: &mut Vec<String>,
+) -> usize {
+    if cells.is_empty() {
+        return 0;
+    }
+
+    let line = if level == LEAF_LEVEL {
+        let mut rows = [[false; LEAF_SIZE as usize]; LEAF_SIZE as usize];
+        for (cell_x, cell_y) in cells {
+            rows[(cell_y - y) as usize][(cell_x - x) as usize] = true;
+        }
+        let mut line: String = rows
+            .iter()
+            .map(|row| {
+                let row: String = row.iter().map(|alive| if *alive { '*' } else { '.' }).collect();
+                format!("{}$", row.trim_end_matches('.'))
+            })
+            .collect();
+        while line.ends_with("$$") {
+            line.pop();
+        }
+        line
+    } else {
+        let half = 1_i128 << (level - 1);
+        let mut quadrants: [Vec<(i128, i128)>; 4] = Default::default();
+        for (cell_x, cell_y) in cells {
+            let quadrant = (cell_x >= x + half) as usize + 2 * (cell_y >= y + half) as usize;
+            quadrants[quadrant].push((cell_x, cell_y));
+        }
+        let offsets = [(0, 0), (half, 0), (0, half), (half, half)];
+        let mut children = [0; 4];
+        for (index, quadrant) in quadrants.into_iter().enumerate() {
+            let (x_offset, y_offset) = offsets[index];
+            children[index] = build_node(quadrant, level - 1, x + x_offset, y + y_offset, node_ids, lines);
+        }
+        format!("{} {} {} {} {}", level, children[0], children[1], children[2], children[3])
+    };
+
+    if let Some(id) = node_ids.get(&line) {
+        return *id;
+    }
+    lines.push(line.clone());
+    node_ids.insert(line, lines.len());
+    return lines.len();
+}
@@ -1,38 +1,154 @@
+#![allow(clippy::needless_return)]
 extern crate crossterm;
-use crate::design_world::DesignWorld;
+use crate::design_world::{DesignWorld, FillSettings};
+use crate::frame_buffer::{DiffRenderer, FrameBuffer};
 use crate::design_world_display::DesignWorldDisplay;
 
 mod tile;
+mod random;
+mod cli_args;
+mod pattern_file;
+mod rle;
+mod plaintext;
+mod life_format;
+mod macrocell;
+mod snapshot;
+mod pattern_library;
+mod transform;
+mod frame_buffer;
+mod zoom;
+mod drawing;
+mod edit_history;
+mod prompt;
+mod rule;
+mod boundary;
+mod sparse_world;
+mod hashlife;
+mod bit_grid;
+mod chunk_stripe;
 mod design_world;
 mod design_world_display;
+mod settings;
+mod settings_display;
+mod view_limits;
 mod world;
 mod world_display;
+mod test_rule;
+mod test_boundary;
+mod test_sparse_world;
+mod test_hashlife;
+mod test_bit_grid;
+mod test_chunk_stripe;
+mod test_cli_args;
+mod test_pattern_file;
+mod test_settings;
+mod test_rle;
+mod test_macrocell;
+mod test_snapshot;
+mod test_pattern_library;
+mod test_design_world;
+mod test_transform;
+mod test_edit_history;
+mod test_drawing;
+mod test_viewport;
+mod test_play_controls;
+mod test_frame_buffer;
+mod test_view_limits;
 
 use std::io::{stdout, Stdout};
+use std::path::PathBuf;
+use std::process;
 use crossterm::{
     execute,
     cursor,
     style::Print,
-    event::{Event, KeyCode},
-    event::{KeyEvent, KeyModifiers, poll, read},
+    event::{Event, KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    event::{DisableMouseCapture, EnableMouseCapture, KeyEvent, KeyModifiers, poll, read},
     terminal::{
         Clear,
         ClearType,
         disable_raw_mode,
         enable_raw_mode,
+        size,
     },
 };
 use std::{
-    thread::sleep,
-    time::{Duration, Instant},
+    thread::{available_parallelism, sleep},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use world_display::{MoveDirections, GameWorldDisplay};
-use crate::world::World;
+use crate::pattern_file::{
+    load_pattern, load_world_cells, normalize, save_pattern, save_world_cells, PatternInfo,
+};
+use crate::prompt::{PromptResult, TextPrompt};
+use crate::boundary::BoundaryMode;
+use crate::cli_args::{CliArgs, USAGE};
+use crate::random::SeededRandom;
+use crate::settings::SettingsForm;
+use crate::snapshot::Snapshot;
+use crate::pattern_library::PATTERN_LIBRARY;
+use crate::transform::Transform;
+use crate::settings_display::SettingsDisplay;
+use crate::view_limits::ViewLimits;
+use crate::world::{StorageKind, World};
+
+const DESIGN_WORLD_MAX_X: usize = 125;
+const DESIGN_WORLD_MAX_Y: usize = 50;
+const SEEDED_SOUP_DENSITY: f64 = 0.35;
+/// How often a paused world redraws and reads input.
+const PAUSED_FRAME_INTERVAL_MS: i32 = 32;
+/// Time each frame spends on queued generations before drawing and reading input again.
+const PENDING_GENERATIONS_BUDGET_MS: u64 = 25;
+/// Lines above the game view: the timings and the viewport line.
+const GAME_HEADER_LINES: usize = 8;
+const DEFAULT_SNAPSHOT_PATH: &str = "game_of_life.snapshot";
+
+/// What a `TextPrompt` does with the text once it's submitted.
+enum PromptPurpose {
+    LoadPattern,
+    SaveDesign,
+    LoadWorld,
+    SaveWorld,
+    SaveSnapshot,
+    LoadSnapshot,
+    RandomFill,
+    AdvanceGenerations,
+    GoTo,
+}
+
+/// Where the pattern being placed in the editor came from.
+#[derive(Clone, Copy)]
+enum StampSource {
+    Library(usize),
+    Clipboard,
+}
 
 
 fn main() {
     let empty_string = "";
+    let cli_args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(cli_args) => cli_args,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+    if cli_args.show_help {
+        print!("{}", USAGE);
+        return;
+    }
+    let pattern = match &cli_args.pattern {
+        Some(path) => match load_pattern(path) {
+            Ok(loaded) => Some(loaded),
+            Err(error) => {
+                eprintln!("error: couldn't load {}: {}", path.display(), error);
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
     enable_raw_mode().unwrap();
     let mut stdout = stdout();
 
@@ -40,83 +156,127 @@ fn main() {
     let mut edit_game_settings = false;
     let mut design_world = true;
     let mut is_game_running = true;
+    let mut settings_form: Option<SettingsForm> = None;
+    let mut prompt: Option<(PromptPurpose, TextPrompt)> = None;
+    let mut status_message = String::new();
+    let mut stamp_source: Option<StampSource> = None;
+    let mut drag_anchor: Option<(u16, u16)> = None;
+    let mut game_renderer = DiffRenderer::new();
 
-    // @TODO make these values for world size allowed to passed in via args
-    // @TODO also make the values editable during the game loop, they can already be resized
-    // safely just need to add keybindings.
     let mut world = World::new(
-        250,
-        250,
-        75,
-        50,
-        36,
-        0
+        cli_args.width,
+        cli_args.height,
+        cli_args.chunk_width,
+        cli_args.chunk_height,
+        cli_args.interval_ms,
+        0,
+        cli_args.rule
+            .or_else(|| pattern.as_ref().and_then(|(_, info)| info.rule))
+            .unwrap_or_default(),
+        cli_args.storage_kind,
+    );
+    world.boundary_mode = cli_args.boundary_mode;
+    world.pan_step = cli_args.pan_step;
+    world.worker_threads = cli_args.worker_threads.unwrap_or_else(|| {
+        available_parallelism().map(|threads| threads.get()).unwrap_or(1)
+    });
+    let mut design_world_instance = DesignWorld::new(
+        cli_args.width.min(DESIGN_WORLD_MAX_X) as u16,
+        cli_args.height.min(DESIGN_WORLD_MAX_Y) as u16,
     );
-    let mut design_world_instance = DesignWorld::new(125, 50);
+    if let Some(seed) = cli_args.seed {
+        design_world_instance.fill_region(&mut SeededRandom::new(seed), SEEDED_SOUP_DENSITY);
+    }
+    if let Some((pattern, _)) = &pattern {
+        design_world_instance.stamp_centered(pattern);
+    }
     
-    let mut current_generation = 1;
     let mut how_long_we_slept = 0;
     let mut how_long_rendering_took = 0;
     let mut how_long_generation_took = 0;
     let mut how_long_input_took = 0;
     let mut how_long_a_frame_took = 0;
     let mut sleep_duration_ms;
+
+    // The view and design map shrink to fit the terminal, and grow back up to these.
+    let mut view_limits = ViewLimits::of(&world, &design_world_instance);
+    let mut terminal_size = size().ok();
+    let mut fitted_terminal_size = None;
     
-    execute!(stdout, cursor::Hide, Print("")).unwrap();
+    execute!(stdout, cursor::Hide, EnableMouseCapture, Print("")).unwrap();
     while is_game_running {
         let now_total = Instant::now();
         let mut now = Instant::now();
+        if terminal_size != fitted_terminal_size {
+            if let Some(terminal_size) = terminal_size {
+                view_limits.fit(
+                    terminal_size,
+                    GAME_HEADER_LINES + 1,
+                    DesignWorldDisplay::HEADER_LINES as usize + 1,
+                    &mut world,
+                    &mut design_world_instance,
+                );
+            }
+            fitted_terminal_size = terminal_size;
+            execute!(stdout, Clear(ClearType::All)).unwrap();
+            game_renderer.invalidate();
+        }
         if edit_game_settings {
-            // @TODO: Add more stuff here to edit game values like:
-            //      world.x_size
-            //      world.y_size
-            //      world.chunk_x_size
-            //      world.chunk_y_size
-            //      world.sleep_duration
-            execute!(stdout, Clear(ClearType::FromCursorUp), cursor::MoveTo(0, 0),
-                Print(
-                    "Howdy howdy\n\
-                    return: go back\n"
-                )
-            ).unwrap();
-            if poll(Duration::from_millis((10) as u64)).unwrap() {
-                let key_event = read().unwrap();
-                match key_event {
-                    Event::Key(
-                        KeyEvent {
-                            code: KeyCode::Backspace,
-                            modifiers: KeyModifiers::NONE,
-                            ..
-                        }) => {
-                        edit_game_settings = false;
-                        design_world = true;
-                        continue;
-                    }
-                    _ => (),
-                }
+            let form = settings_form.get_or_insert_with(|| {
+                SettingsForm::new(&world, &view_limits)
+            });
+            SettingsDisplay::print_settings(&mut stdout, form);
+            game_renderer.invalidate();
+            if handle_settings_input(form, &mut world, &mut design_world_instance, &mut terminal_size) {
+                // Applying sets the sizes asked for, which may need shrinking to the terminal.
+                view_limits = ViewLimits::of(&world, &design_world_instance);
+                fitted_terminal_size = None;
+                settings_form = None;
+                edit_game_settings = false;
+                design_world = true;
+                continue;
             }
             sleep_duration_ms = 10;
         }
         else if design_world {
             let now = Instant::now();
-            // @TODO: Add more functionality here for editing, mainly QOL life stuff
-            //      1) Ability to copy a section and paste it else where on the world
-            //      2) Clear the design map, so you don't need to reset the app to clear it or untoggle everything manually.
-
             // Render
             DesignWorldDisplay::print_design_world(&mut stdout, &design_world_instance);
+            game_renderer.invalidate();
+            match stamp_source {
+                Some(source) => print_stamp_line(source),
+                None => print_prompt_or_status(&prompt, &status_message),
+            }
 
-            // handle input
-            (is_game_running, design_world, edit_game_settings) = handle_design_world_input(
-                &mut design_world_instance
-            );
-            
-            // init the game world w/ our edits
-            if !design_world {
-                world.reset_game_world(design_world_instance.marked_positions.iter());
-                current_generation = 1;
+            // handle input, a restored snapshot goes straight back to its running world
+            if prompt.is_some() {
+                if let Some(message) = handle_prompt_input(
+                    &mut prompt,
+                    &mut world,
+                    &mut design_world_instance,
+                    &mut design_world,
+                    &mut terminal_size,
+                    &mut view_limits,
+                    &mut fitted_terminal_size,
+                ) {
+                    status_message = message;
+                }
+            } else if stamp_source.is_some() {
+                handle_stamp_input(&mut stamp_source, &mut design_world_instance, &mut terminal_size);
+            } else {
+                (is_game_running, design_world, edit_game_settings) = handle_design_world_input(
+                    &mut design_world_instance,
+                    &mut prompt,
+                    &mut stamp_source,
+                    &mut terminal_size,
+                );
+
+                // init the game world w/ our edits
+                if !design_world {
+                    world.reset_game_world(design_world_instance.all_marks());
+                }
             }
-            sleep_duration_ms = (32 as i32) - (now.elapsed().as_millis() as i32);
+            sleep_duration_ms = 32 - (now.elapsed().as_millis() as i32);
             if sleep_duration_ms < 0 {
                 sleep_duration_ms = 0;
             }
@@ -124,34 +284,64 @@ fn main() {
         else {
             // Render
             render_game_world(
-                current_generation,
                 how_long_we_slept,
                 how_long_a_frame_took,
                 how_long_rendering_took,
                 how_long_generation_took,
                 how_long_input_took,
                 &world,
+                &prompt_or_status_line(&prompt, &status_message),
+                terminal_size.map(|(columns, _)| columns as usize),
+                &mut game_renderer,
                 &mut stdout
             );
             how_long_rendering_took = now.elapsed().as_millis() as i128;
             now = Instant::now();
-            
+
+            // The world holds still while a file name is typed.
+            if prompt.is_some() {
+                if let Some(message) = handle_prompt_input(
+                    &mut prompt,
+                    &mut world,
+                    &mut design_world_instance,
+                    &mut design_world,
+                    &mut terminal_size,
+                    &mut view_limits,
+                    &mut fitted_terminal_size,
+                ) {
+                    status_message = message;
+                }
+                continue;
+            }
+
             // handle input
-            (is_game_running, design_world, reset_game) = handle_game_play_input(&mut world);
+            (is_game_running, design_world, reset_game) = handle_game_play_input(
+                &mut world,
+                &mut prompt,
+                &mut drag_anchor,
+                &mut terminal_size,
+            );
             how_long_input_took = now.elapsed().as_millis() as i128;
             now = Instant::now();
             if reset_game {
-                world.reset_game_world(design_world_instance.marked_positions.iter());
-                current_generation = 1;
+                world.reset_game_world(design_world_instance.all_marks());
                 continue;
             }
 
-            // handle update
-            handle_gameplay_loop(&mut world);
-            how_long_generation_took = now.elapsed().as_millis() as i128;
-
-            current_generation += 1;
-            sleep_duration_ms = world.frame_interval_ms as i32;
+            // handle update, queued generations run even while paused and a paused world
+            // otherwise only redraws and reads input
+            if world.pending_generations > 0 {
+                world.step_pending_generations(Duration::from_millis(PENDING_GENERATIONS_BUDGET_MS));
+                how_long_generation_took = now.elapsed().as_millis() as i128;
+                sleep_duration_ms = 0;
+            } else if world.paused {
+                how_long_generation_took = 0;
+                sleep_duration_ms = PAUSED_FRAME_INTERVAL_MS;
+            } else {
+                handle_gameplay_loop(&mut world);
+                how_long_generation_took = now.elapsed().as_millis() as i128;
+                sleep_duration_ms = if world.uncapped { 0 } else { world.frame_interval_ms as i32 };
+            }
         }
 
         how_long_a_frame_took = now_total.elapsed().as_millis() as i128;
@@ -159,41 +349,142 @@ fn main() {
         if how_long_we_slept > 0 {
             sleep(Duration::from_millis(how_long_we_slept as u64));
         }
-        how_long_a_frame_took = how_long_a_frame_took + how_long_we_slept
+        how_long_a_frame_took += how_long_we_slept
     }
 
-    execute!(stdout, Clear(ClearType::All), Print(empty_string)).unwrap();
+    execute!(stdout, Clear(ClearType::All), DisableMouseCapture, Print(empty_string)).unwrap();
     disable_raw_mode().unwrap();
 }
 
-fn handle_game_play_input(world: &mut World) -> (bool, bool, bool) {
-    if poll(Duration::from_millis((world.allotted_read_input_time) as u64)).unwrap() {
-        let key_event = read().unwrap();
+fn handle_game_play_input(
+    world: &mut World,
+    prompt: &mut Option<(PromptPurpose, TextPrompt)>,
+    drag_anchor: &mut Option<(u16, u16)>,
+    terminal_size: &mut Option<(u16, u16)>,
+) -> (bool, bool, bool) {
+    if let Some(key_event) = read_event(world.allotted_read_input_time as u64, terminal_size) {
+        open_snapshot_prompt(&key_event, prompt);
         match key_event {
             Event::Key(KeyEvent {
                            code: KeyCode::Char('w'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => world.move_chunk(MoveDirections::Up),
+                       }) => world.move_viewport(MoveDirections::Up, world.pan_step as i64),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('W'), ..
+                       }) => page_viewport(world, MoveDirections::Up),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('a'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => world.move_chunk(MoveDirections::Left),
+                       }) => world.move_viewport(MoveDirections::Left, world.pan_step as i64),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('A'), ..
+                       }) => page_viewport(world, MoveDirections::Left),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('s'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => world.move_chunk(MoveDirections::Down),
+                       }) => world.move_viewport(MoveDirections::Down, world.pan_step as i64),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('S'), ..
+                       }) => page_viewport(world, MoveDirections::Down),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('d'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => world.move_chunk(MoveDirections::Right),
+                       }) => world.move_viewport(MoveDirections::Right, world.pan_step as i64),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('D'), ..
+                       }) => page_viewport(world, MoveDirections::Right),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('e'),
                            modifiers: KeyModifiers::CONTROL, ..
                        }) => return (true, true, false),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('b'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => cycle_world_edges(world),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('g'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => toggle_bit_packed_storage(world),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('f'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.jump(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char(']'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.larger_jump(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('['),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.smaller_jump(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char(' '),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.paused = !world.paused,
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('.'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                world.paused = true;
+                world.advance_generations(1);
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('n'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                *prompt = Some((PromptPurpose::AdvanceGenerations, TextPrompt::new("Advance generations: ")));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Esc, ..
+                       }) => world.pending_generations = 0,
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('+' | '='),
+                           modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT, ..
+                       }) => world.faster(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('-'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.slower(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('u'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.uncapped = !world.uncapped,
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('c'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                world.centre_on_population();
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('j'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                *prompt = Some((PromptPurpose::GoTo, TextPrompt::new("Go to x y: ")));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('z'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.zoom_in(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('x'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => world.zoom_out(),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('r'),
                            modifiers: KeyModifiers::NONE, ..
                        }) => return (true, false, true),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('s'),
+                           modifiers: KeyModifiers::CONTROL, ..
+                       }) => {
+                *prompt = Some((PromptPurpose::SaveWorld, TextPrompt::new("Save world as (.rle, .cells, .lif, .mc): ")));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('o'),
+                           modifiers: KeyModifiers::CONTROL, ..
+                       }) => {
+                *prompt = Some((PromptPurpose::LoadWorld, TextPrompt::new("Load world: ")));
+            }
             Event::Key(KeyEvent {
                            code: KeyCode::Char('q'),
                            modifiers: KeyModifiers::CONTROL, ..
@@ -202,17 +493,214 @@ fn handle_game_play_input(world: &mut World) -> (bool, bool, bool) {
                            code: KeyCode::Char('c'),
                            modifiers: KeyModifiers::CONTROL, ..
                        }) => return (false, false, false),
+            Event::Mouse(mouse_event) => handle_game_mouse(world, drag_anchor, mouse_event),
             _ => (),
         }
     }
     return (true, false, false)
 }
 
+/// Waits up to `timeout_ms` for an event. Resizes are taken here for every screen, so the
+/// next frame refits to the new size, and never reach the key handlers.
+fn read_event(timeout_ms: u64, terminal_size: &mut Option<(u16, u16)>) -> Option<Event> {
+    if !poll(Duration::from_millis(timeout_ms)).unwrap() {
+        return None;
+    }
+    return match read().unwrap() {
+        Event::Resize(columns, rows) => {
+            *terminal_size = Some((columns, rows));
+            None
+        }
+        event => Some(event),
+    };
+}
+
+/// Jumps the view by its own width or height.
+fn page_viewport(world: &mut World, move_direction: MoveDirections) {
+    let (width, height) = world.viewport_size();
+    let distance = match move_direction {
+        MoveDirections::Left | MoveDirections::Right => width,
+        MoveDirections::Up | MoveDirections::Down => height,
+    };
+    world.move_viewport(move_direction, distance);
+}
+
+/// Dragging pans the view with the cells under the mouse, and the wheel zooms.
+fn handle_game_mouse(world: &mut World, drag_anchor: &mut Option<(u16, u16)>, mouse_event: MouseEvent) {
+    let position = (mouse_event.column, mouse_event.row);
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => *drag_anchor = Some(position),
+        MouseEventKind::Drag(MouseButton::Left) => {
+            let Some(anchor) = *drag_anchor else {
+                return;
+            };
+            let (char_width, char_height) = world.zoom.cells_per_char();
+            world.pan(
+                (anchor.0 as i64 - position.0 as i64) * char_width as i64,
+                (anchor.1 as i64 - position.1 as i64) * char_height as i64,
+            );
+            *drag_anchor = Some(position);
+        }
+        MouseEventKind::Up(MouseButton::Left) => *drag_anchor = None,
+        MouseEventKind::ScrollUp => world.zoom_in(),
+        MouseEventKind::ScrollDown => world.zoom_out(),
+        _ => (),
+    }
+}
+
+/// Clicking uses the drawing tool on the cell under the mouse and dragging paints.
+fn handle_design_mouse(design_world_instance: &mut DesignWorld, mouse_event: MouseEvent) {
+    if let MouseEventKind::Up(MouseButton::Left) = mouse_event.kind {
+        design_world_instance.end_drag();
+        return;
+    }
+    let Some(position) = DesignWorldDisplay::position_at(
+        design_world_instance,
+        mouse_event.column,
+        mouse_event.row,
+    ) else {
+        return;
+    };
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => design_world_instance.click(position),
+        MouseEventKind::Drag(MouseButton::Left) => design_world_instance.drag_to(position),
+        _ => (),
+    }
+}
+
+/// Steps through every edge behaviour, with the unbounded plane after the cross-surface.
+fn cycle_world_edges(world: &mut World) {
+    if world.storage_kind == StorageKind::Unbounded {
+        world.set_storage_kind(StorageKind::Tiles);
+        world.boundary_mode = BoundaryMode::Dead;
+    } else if world.boundary_mode == BoundaryMode::CrossSurface {
+        world.set_storage_kind(StorageKind::Unbounded);
+    } else {
+        world.boundary_mode = world.boundary_mode.next();
+    }
+}
+
+/// Swaps a bounded world between per cell tiles and the bit-packed grid, unbounded worlds
+/// have no dense grid to switch to.
+fn toggle_bit_packed_storage(world: &mut World) {
+    match world.storage_kind {
+        StorageKind::Tiles => world.set_storage_kind(StorageKind::BitPacked),
+        StorageKind::BitPacked => world.set_storage_kind(StorageKind::Tiles),
+        StorageKind::Unbounded => {}
+    }
+}
+
+/// Edits the settings form, returning `true` once it has been applied or cancelled.
+fn handle_settings_input(
+    form: &mut SettingsForm,
+    world: &mut World,
+    design_world_instance: &mut DesignWorld,
+    terminal_size: &mut Option<(u16, u16)>,
+) -> bool {
+    if let Some(Event::Key(KeyEvent { code, .. })) = read_event(10, terminal_size) {
+        match code {
+            KeyCode::Up | KeyCode::BackTab => form.move_selection(-1),
+            KeyCode::Down | KeyCode::Tab => form.move_selection(1),
+            KeyCode::Char(c) => form.push_char(c),
+            KeyCode::Backspace => form.pop_char(),
+            KeyCode::Enter => return form.apply(world, design_world_instance),
+            KeyCode::Esc => return true,
+            _ => (),
+        }
+    }
+    return false;
+}
+
+/// Places the previewed stamp, from the library or the clipboard, the cursor keeps moving
+/// so it can be lined up and it can be turned over before each stamp.
+fn handle_stamp_input(
+    stamp_source: &mut Option<StampSource>,
+    design_world_instance: &mut DesignWorld,
+    terminal_size: &mut Option<(u16, u16)>,
+) {
+    let Some(source) = *stamp_source else {
+        return;
+    };
+    let Some(Event::Key(KeyEvent { code, .. })) = read_event(10, terminal_size) else {
+        return;
+    };
+    let is_clipboard = matches!(source, StampSource::Clipboard);
+    let library_size = PATTERN_LIBRARY.len();
+    let transform = match code {
+        KeyCode::Char('r') => Some(Transform::RotateClockwise),
+        KeyCode::Char('R') => Some(Transform::RotateCounterClockwise),
+        KeyCode::Char('f') => Some(Transform::FlipHorizontal),
+        KeyCode::Char('g') => Some(Transform::FlipVertical),
+        KeyCode::Char('t') => Some(Transform::Transpose),
+        _ => None,
+    };
+    if let Some(transform) = transform {
+        design_world_instance.transform_preview(transform, is_clipboard);
+        return;
+    }
+    match (code, source) {
+        (KeyCode::Up | KeyCode::BackTab, StampSource::Library(selection)) => {
+            let selection = (selection + library_size - 1) % library_size;
+            select_library_pattern(stamp_source, design_world_instance, selection);
+        }
+        (KeyCode::Down | KeyCode::Tab, StampSource::Library(selection)) => {
+            let selection = (selection + 1) % library_size;
+            select_library_pattern(stamp_source, design_world_instance, selection);
+        }
+        (KeyCode::Char('w'), _) => design_world_instance.move_chunk(MoveDirections::Down),
+        (KeyCode::Char('a'), _) => design_world_instance.move_chunk(MoveDirections::Left),
+        (KeyCode::Char('s'), _) => design_world_instance.move_chunk(MoveDirections::Up),
+        (KeyCode::Char('d'), _) => design_world_instance.move_chunk(MoveDirections::Right),
+        (KeyCode::Enter, _) => design_world_instance.stamp_preview(),
+        (KeyCode::Char('z'), _) => {
+            design_world_instance.undo();
+        }
+        (KeyCode::Char('y'), _) => {
+            design_world_instance.redo();
+        }
+        (KeyCode::Esc, _) => {
+            *stamp_source = None;
+            design_world_instance.preview = None;
+        }
+        _ => (),
+    }
+}
+
+fn select_library_pattern(
+    stamp_source: &mut Option<StampSource>,
+    design_world_instance: &mut DesignWorld,
+    selection: usize,
+) {
+    *stamp_source = Some(StampSource::Library(selection));
+    design_world_instance.preview = Some(PATTERN_LIBRARY[selection].pattern());
+}
+
+fn print_stamp_line(stamp_source: StampSource) {
+    let keys = "r,R: rotate, f,g: mirror, t: transpose, enter: stamp, z,y: undo/redo, esc: close";
+    match stamp_source {
+        StampSource::Library(selection) => {
+            let library_pattern = &PATTERN_LIBRARY[selection];
+            println!(
+                "Library {}/{}: {} - {} :: up,down: choose, {}",
+                selection + 1,
+                PATTERN_LIBRARY.len(),
+                library_pattern.name,
+                library_pattern.description,
+                keys
+            );
+        }
+        StampSource::Clipboard => println!("Pasting the clipboard :: {}", keys),
+    }
+}
+
 fn handle_design_world_input(
-    design_world_instance: &mut DesignWorld
+    design_world_instance: &mut DesignWorld,
+    prompt: &mut Option<(PromptPurpose, TextPrompt)>,
+    stamp_source: &mut Option<StampSource>,
+    terminal_size: &mut Option<(u16, u16)>,
 ) -> (bool, bool, bool) {
-    if poll(Duration::from_millis((10) as u64)).unwrap() {
-        let key_event = read().unwrap();
+    if let Some(key_event) = read_event(10, terminal_size) {
+        open_snapshot_prompt(&key_event, prompt);
         match key_event {
             Event::Key(
                 KeyEvent {
@@ -220,21 +708,7 @@ fn handle_design_world_input(
                     modifiers: KeyModifiers::NONE,
                     ..
                 }) => {
-                let current_tile = design_world_instance
-                    .marked_positions
-                    .get(&design_world_instance.current_position);
-
-                if *current_tile.unwrap_or(&false) {
-                    design_world_instance.marked_positions.insert(
-                        design_world_instance.current_position,
-                        false,
-                    );
-                } else {
-                    design_world_instance.marked_positions.insert(
-                        design_world_instance.current_position,
-                        true,
-                    );
-                }
+                design_world_instance.use_tool();
             }
             Event::Key(KeyEvent {
                            code: KeyCode::Char('e'),
@@ -251,61 +725,357 @@ fn handle_design_world_input(
                            modifiers: KeyModifiers::CONTROL,
                            ..
                        }) => return (true, false, false),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('o'),
+                           modifiers: KeyModifiers::CONTROL,
+                           ..
+                       }) => {
+                *prompt = Some((PromptPurpose::LoadPattern, TextPrompt::new("Load pattern: ")));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('s'),
+                           modifiers: KeyModifiers::CONTROL,
+                           ..
+                       }) => {
+                *prompt = Some((PromptPurpose::SaveDesign, TextPrompt::new("Save design as (.rle, .cells, .lif, .mc): ")));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('v'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                design_world_instance.selection_anchor = match design_world_instance.selection_anchor {
+                    Some(_) => None,
+                    None => Some(design_world_instance.current_position),
+                };
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('c'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => design_world_instance.copy_selection(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('x'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => design_world_instance.cut_selection(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('p'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                if let Some(clipboard) = &design_world_instance.clipboard {
+                    design_world_instance.preview = Some(clipboard.clone());
+                    *stamp_source = Some(StampSource::Clipboard);
+                }
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Esc,
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                design_world_instance.selection_anchor = None;
+                design_world_instance.tool_anchor = None;
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('e'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => design_world_instance.clear_region(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('r'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                let label = format!("Random fill as density [seed] ({}, random seed): ", SEEDED_SOUP_DENSITY);
+                *prompt = Some((PromptPurpose::RandomFill, TextPrompt::new(&label)));
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('i'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => design_world_instance.invert_region(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('z'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                design_world_instance.undo();
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('y'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                design_world_instance.redo();
+            }
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('t'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => design_world_instance.next_tool(),
+            Event::Key(KeyEvent {
+                           code: KeyCode::Char('l'),
+                           modifiers: KeyModifiers::NONE, ..
+                       }) => {
+                select_library_pattern(stamp_source, design_world_instance, 0);
+            }
             Event::Key(KeyEvent {
                            code: KeyCode::Char('w'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => design_world_instance.move_chunk(MoveDirections::Down),
+                       }) => design_world_instance.move_drawing(MoveDirections::Down),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('a'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => design_world_instance.move_chunk(MoveDirections::Left),
+                       }) => design_world_instance.move_drawing(MoveDirections::Left),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('s'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => design_world_instance.move_chunk(MoveDirections::Up),
+                       }) => design_world_instance.move_drawing(MoveDirections::Up),
             Event::Key(KeyEvent {
                            code: KeyCode::Char('d'),
                            modifiers: KeyModifiers::NONE, ..
-                       }) => design_world_instance.move_chunk(MoveDirections::Right),
+                       }) => design_world_instance.move_drawing(MoveDirections::Right),
+            Event::Mouse(mouse_event) => handle_design_mouse(design_world_instance, mouse_event),
             _ => (),
         }
     }
     return (true, true, false);
 }
 
+fn is_snapshot(purpose: &PromptPurpose) -> bool {
+    return matches!(purpose, PromptPurpose::SaveSnapshot | PromptPurpose::LoadSnapshot);
+}
+
+/// Opens the snapshot prompts, shared by the game and design loops.
+fn open_snapshot_prompt(key_event: &Event, prompt: &mut Option<(PromptPurpose, TextPrompt)>) {
+    let purpose = match key_event {
+        Event::Key(KeyEvent {
+                       code: KeyCode::Char('w'),
+                       modifiers: KeyModifiers::CONTROL, ..
+                   }) => PromptPurpose::SaveSnapshot,
+        Event::Key(KeyEvent {
+                       code: KeyCode::Char('r'),
+                       modifiers: KeyModifiers::CONTROL, ..
+                   }) => PromptPurpose::LoadSnapshot,
+        _ => return,
+    };
+    let label = match purpose {
+        PromptPurpose::SaveSnapshot => format!("Save snapshot as ({}): ", DEFAULT_SNAPSHOT_PATH),
+        _ => format!("Restore snapshot from ({}): ", DEFAULT_SNAPSHOT_PATH),
+    };
+    *prompt = Some((purpose, TextPrompt::new(&label)));
+}
+
+fn print_prompt_or_status(prompt: &Option<(PromptPurpose, TextPrompt)>, status_message: &str) {
+    println!("{}", prompt_or_status_line(prompt, status_message));
+}
+
+/// Feeds a key to the open prompt, returning the new status message once it's been
+/// submitted or cancelled.
+fn handle_prompt_input(
+    prompt: &mut Option<(PromptPurpose, TextPrompt)>,
+    world: &mut World,
+    design_world_instance: &mut DesignWorld,
+    design_world: &mut bool,
+    terminal_size: &mut Option<(u16, u16)>,
+    view_limits: &mut ViewLimits,
+    fitted_terminal_size: &mut Option<(u16, u16)>,
+) -> Option<String> {
+    let Some(Event::Key(KeyEvent { code, .. })) = read_event(10, terminal_size) else {
+        return None;
+    };
+    let (purpose, text_prompt) = prompt.as_mut()?;
+    let input = match text_prompt.handle_key(code) {
+        PromptResult::Editing => return None,
+        PromptResult::Cancelled => {
+            *prompt = None;
+            return Some(String::new());
+        }
+        PromptResult::Submitted(input) if input.is_empty() && is_snapshot(purpose) => {
+            DEFAULT_SNAPSHOT_PATH.to_string()
+        }
+        PromptResult::Submitted(input) => input,
+    };
+    let path = PathBuf::from(&input);
+
+    let message = match purpose {
+        PromptPurpose::LoadPattern => match load_pattern(&path) {
+            Ok((pattern, info)) => {
+                design_world_instance.stamp(&pattern, design_world_instance.current_position);
+                if let Some(rule) = info.rule {
+                    world.rule = rule;
+                }
+                format!(
+                    "Loaded {} ({} cells, rule {})",
+                    info.name.unwrap_or_else(|| path.display().to_string()),
+                    pattern.len(),
+                    world.rule
+                )
+            }
+            Err(error) => format!("Couldn't load {}: {}", path.display(), error),
+        },
+        PromptPurpose::LoadWorld => match load_world_cells(&path) {
+            Ok((cells, info)) => {
+                if let Some(rule) = info.rule {
+                    world.rule = rule;
+                }
+                world.replace_live_cells(cells);
+                world.current_generation = info.generation.unwrap_or(1);
+                format!("Loaded {} ({} cells)", path.display(), world.population())
+            }
+            Err(error) => format!("Couldn't load {}: {}", path.display(), error),
+        },
+        PromptPurpose::SaveWorld => {
+            let info = PatternInfo {
+                rule: Some(world.rule),
+                generation: Some(world.current_generation),
+                ..PatternInfo::default()
+            };
+            match save_world_cells(&path, world.live_cells(), &info) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(error) => format!("Couldn't save {}: {}", path.display(), error),
+            }
+        }
+        PromptPurpose::SaveSnapshot => {
+            match Snapshot::capture(world, design_world_instance, view_limits).save(&path) {
+                Ok(()) => format!("Saved snapshot {}", path.display()),
+                Err(error) => format!("Couldn't save snapshot {}: {}", path.display(), error),
+            }
+        }
+        PromptPurpose::LoadSnapshot => match Snapshot::load(&path) {
+            Ok(snapshot) => {
+                snapshot.restore(world, design_world_instance);
+                // The snapshot's sizes become the limits, shrunk to the terminal on the next frame.
+                *view_limits = ViewLimits::of(world, design_world_instance);
+                *fitted_terminal_size = None;
+                *design_world = false;
+                format!("Restored {} at generation {}", path.display(), world.current_generation)
+            }
+            Err(error) => format!("Couldn't load snapshot {}: {}", path.display(), error),
+        },
+        PromptPurpose::RandomFill => {
+            match FillSettings::parse(&input, SEEDED_SOUP_DENSITY, time_seed()) {
+                Ok(settings) => {
+                    design_world_instance.fill_region(&mut SeededRandom::new(settings.seed), settings.density);
+                    format!("Filled at density {} with seed {}", settings.density, settings.seed)
+                }
+                Err(message) => message,
+            }
+        }
+        PromptPurpose::AdvanceGenerations => match World::parse_generations(&input) {
+            Ok(generations) => {
+                world.advance_generations(generations);
+                if world.pending_generations > 0 {
+                    format!("Advancing {} generations, esc stops", generations)
+                } else {
+                    format!("Advanced {} generations to generation {}", generations, world.current_generation)
+                }
+            }
+            Err(message) => message,
+        },
+        PromptPurpose::GoTo => match parse_coordinate(&input) {
+            Some(position) => {
+                world.centre_on(position);
+                format!("Centred on ({}, {})", position.0, position.1)
+            }
+            None => format!("Expected 'x y', got {}", input),
+        },
+        PromptPurpose::SaveDesign => {
+            let info = PatternInfo { rule: Some(world.rule), ..PatternInfo::default() };
+            let cells = design_world_instance.marked_cells();
+            match normalize(cells).and_then(|pattern| save_pattern(&path, &pattern, &info)) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(error) => format!("Couldn't save {}: {}", path.display(), error),
+            }
+        }
+    };
+    *prompt = None;
+    return Some(message);
+}
+
+/// Reads `x y`, with an optional comma between them.
+fn parse_coordinate(input: &str) -> Option<(i64, i64)> {
+    let mut numbers = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<i64>());
+    return match (numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some((x, y)),
+        _ => None,
+    };
+}
+
+/// A seed for fills that weren't given one, reported back so the fill can be repeated.
+fn time_seed() -> u64 {
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0);
+}
+
 fn handle_gameplay_loop(world: &mut World) {
     let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
     let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
     world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
 }
 
+/// Whether the world is running and how fast, with the keys that change it.
+fn play_state(world: &World) -> String {
+    let state = if world.pending_generations > 0 {
+        format!("Advancing, {} generations left (esc: stop)", world.pending_generations)
+    } else if world.paused {
+        "Paused".to_string()
+    } else {
+        "Running".to_string()
+    };
+    let speed = if world.uncapped {
+        "uncapped".to_string()
+    } else {
+        format!("{}ms per generation", world.frame_interval_ms)
+    };
+    return format!(
+        "{}, {} (space: pause, .: step, n: advance N, f: jump 2^{} ([/]: smaller/larger), +/-: faster/slower, u: uncapped, z/x: zoom in/out, c: centre, j: go to, W/A/S/D: page)",
+        state, speed, world.jump_exponent
+    );
+}
+
+/// Lays out the timings, the view and the prompt or status line as one frame and draws
+/// whatever changed since the last one. Lines wider than the terminal are cut off rather
+/// than wrapped.
+#[allow(clippy::too_many_arguments)]
 fn render_game_world(
-    current_generation: u128,
     how_long_we_slept: i128,
     how_long_a_frame_took: i128,
     how_long_rendering_took: i128,
     how_long_generation_took: i128,
     how_long_input_took: i128,
     world: &World,
+    bottom_line: &str,
+    columns: Option<usize>,
+    renderer: &mut DiffRenderer,
     stdout: &mut Stdout,
 ) {
-    execute!(stdout, Clear(ClearType::FromCursorUp), cursor::MoveTo(0, 0), Print(
-        format!(
-            "Current generation: {}\n  \
-             Read_time: {}ms\n  \
-             Rendering took {}ms\n  \
-             Generation_took: {}ms\n  \
-             Sleep Time: {}ms\n  \
-             Requested frame interval: {}\n  \
-             Total Frame Time: {}ms\n",
-            current_generation,
-            how_long_input_took,
-            how_long_rendering_took,
-            how_long_generation_took,
-            how_long_we_slept,
-            world.frame_interval_ms,
-            how_long_a_frame_took
-        ),
-    )).unwrap();
-    GameWorldDisplay::print_chunk(&world);
+    let header: [String; GAME_HEADER_LINES] = [
+        format!("Current generation: {} :: {}", world.current_generation, play_state(world)),
+        format!("  Read_time: {}ms", how_long_input_took),
+        format!("  Rendering took {}ms", how_long_rendering_took),
+        format!("  Generation_took: {}ms", how_long_generation_took),
+        format!("  Sleep Time: {}ms", how_long_we_slept),
+        format!("  Requested frame interval: {}", world.frame_interval_ms),
+        format!("  Total Frame Time: {}ms", how_long_a_frame_took),
+        GameWorldDisplay::chunk_header(world),
+    ];
+    let width = header
+        .iter()
+        .chain([bottom_line.to_string()].iter())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(world.chunk_x_size)
+        .min(columns.unwrap_or(usize::MAX));
+    let mut frame = FrameBuffer::new(width, header.len() + world.chunk_y_size + 1);
+    for (row, line) in header.iter().enumerate() {
+        frame.put_text(row, line);
+    }
+    GameWorldDisplay::draw_chunk(world, &mut frame, header.len());
+    frame.put_text(header.len() + world.chunk_y_size, bottom_line);
+    renderer.render(stdout, frame).unwrap();
+}
+
+/// The open prompt with its cursor, or the last status message.
+fn prompt_or_status_line(prompt: &Option<(PromptPurpose, TextPrompt)>, status_message: &str) -> String {
+    return match prompt {
+        Some((_, text_prompt)) => format!("{}{}_", text_prompt.label, text_prompt.input),
+        None => status_message.to_string(),
+    };
 }
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::life_format::{read_life_105, read_life_106, write_life_105, write_life_106};
+use crate::macrocell::{read_macrocell, write_macrocell};
+use crate::plaintext::{read_plaintext, write_plaintext};
+use crate::rle::{read_rle, write_rle};
+use crate::rule::Rule;
+
+/// A pattern read from disk, keyed like `DesignWorld::marked_positions` with the top left
+/// corner of its bounding box moved to `(0, 0)`.
+pub type Pattern = HashMap<(u16, u16), bool>;
+
+/// The name, author, comments and rule some formats carry alongside the cells.
+#[derive(Debug, Default, PartialEq)]
+pub struct PatternInfo {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub comments: Vec<String>,
+    pub rule: Option<Rule>,
+    pub generation: Option<u128>,
+}
+
+#[derive(Debug)]
+pub enum PatternError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+    TooLarge,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Io(error) => write!(f, "{}", error),
+            PatternError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            PatternError::TooLarge => write!(f, "pattern is wider or taller than {} cells", u16::MAX),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl From<std::io::Error> for PatternError {
+    fn from(error: std::io::Error) -> PatternError {
+        return PatternError::Io(error);
+    }
+}
+
+/// The pattern file formats that can be read and written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternFormat {
+    Rle,
+    Plaintext,
+    Life105,
+    Life106,
+    Macrocell,
+}
+
+impl PatternFormat {
+    pub fn from_extension(path: &Path) -> Option<PatternFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        return match extension.as_str() {
+            "rle" => Some(PatternFormat::Rle),
+            "cells" => Some(PatternFormat::Plaintext),
+            // Both Life 1.0x versions share the extension, 1.06 is the more common today.
+            "lif" | "life" => Some(PatternFormat::Life106),
+            "mc" => Some(PatternFormat::Macrocell),
+            _ => None,
+        };
+    }
+
+    /// Picks the format from a file's contents, falling back to its extension when the
+    /// contents don't give it away, then to RLE.
+    pub fn detect(path: &Path, contents: &str) -> PatternFormat {
+        let first_line = contents
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+        if first_line.starts_with("#Life 1.05") {
+            return PatternFormat::Life105;
+        }
+        if first_line.starts_with("#Life 1.06") {
+            return PatternFormat::Life106;
+        }
+        if first_line.starts_with("[M2]") {
+            return PatternFormat::Macrocell;
+        }
+        if first_line.starts_with('!') {
+            return PatternFormat::Plaintext;
+        }
+
+        let body: Vec<&str> = contents
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        if body.first().is_some_and(|line| line.starts_with('x')) {
+            return PatternFormat::Rle;
+        }
+        if let Some(format) = PatternFormat::from_extension(path) {
+            return format;
+        }
+
+        let is_coordinate = |line: &&str| {
+            line.split_whitespace().count() == 2
+                && line.split_whitespace().all(|part| part.parse::<i64>().is_ok())
+        };
+        if !body.is_empty() && body.iter().all(is_coordinate) {
+            return PatternFormat::Life106;
+        }
+        if !body.is_empty() && body.iter().all(|line| line.chars().all(|c| matches!(c, '.' | 'O' | '*'))) {
+            return PatternFormat::Plaintext;
+        }
+        return PatternFormat::Rle;
+    }
+
+    pub fn read(&self, contents: &str) -> Result<(Pattern, PatternInfo), PatternError> {
+        return match self {
+            PatternFormat::Rle => read_rle(contents),
+            PatternFormat::Plaintext => read_plaintext(contents),
+            PatternFormat::Life105 => read_life_105(contents),
+            PatternFormat::Life106 => read_life_106(contents),
+            PatternFormat::Macrocell => {
+                let (cells, info) = read_macrocell(contents)?;
+                Ok((normalize(cells)?, info))
+            }
+        };
+    }
+
+    pub fn write(&self, pattern: &Pattern, info: &PatternInfo) -> String {
+        return match self {
+            PatternFormat::Rle => write_rle(pattern, info),
+            PatternFormat::Plaintext => write_plaintext(pattern, info),
+            PatternFormat::Life105 => write_life_105(pattern, info),
+            PatternFormat::Life106 => write_life_106(pattern, info),
+            PatternFormat::Macrocell => {
+                let cells: Vec<(i64, i64)> = live_cells(pattern)
+                    .iter()
+                    .map(|(x, y)| (*x as i64, *y as i64))
+                    .collect();
+                write_macrocell(&cells, info)
+            }
+        };
+    }
+}
+
+/// Loads a pattern in whichever format `PatternFormat::detect` recognises.
+pub fn load_pattern(path: &Path) -> Result<(Pattern, PatternInfo), PatternError> {
+    let contents = fs::read_to_string(path)?;
+    return PatternFormat::detect(path, &contents).read(&contents);
+}
+
+/// Saves a pattern in the format its extension names, RLE when it names none.
+pub fn save_pattern(path: &Path, pattern: &Pattern, info: &PatternInfo) -> Result<(), PatternError> {
+    let format = PatternFormat::from_extension(path).unwrap_or(PatternFormat::Rle);
+    fs::write(path, format.write(pattern, info))?;
+    return Ok(());
+}
+
+/// Loads cells for a running `World`. Macrocell keeps the exact coordinates it was saved
+/// at, other formats are moved to start at `(0, 0)`.
+pub fn load_world_cells(path: &Path) -> Result<(Vec<(i64, i64)>, PatternInfo), PatternError> {
+    let contents = fs::read_to_string(path)?;
+    let format = PatternFormat::detect(path, &contents);
+    if format == PatternFormat::Macrocell {
+        return read_macrocell(&contents);
+    }
+    let (pattern, info) = format.read(&contents)?;
+    let cells = live_cells(&pattern).iter().map(|(x, y)| (*x as i64, *y as i64)).collect();
+    return Ok((cells, info));
+}
+
+/// Saves the cells of a running `World`, exactly for Macrocell and moved to start at
+/// `(0, 0)` for the formats that can't hold negative or huge coordinates.
+pub fn save_world_cells(
+    path: &Path,
+    cells: Vec<(i64, i64)>,
+    info: &PatternInfo,
+) -> Result<(), PatternError> {
+    if PatternFormat::from_extension(path) == Some(PatternFormat::Macrocell) {
+        fs::write(path, write_macrocell(&cells, info))?;
+        return Ok(());
+    }
+    return save_pattern(path, &normalize(cells)?, info);
+}
+
+/// The live cells of a pattern in reading order, top to bottom then left to right.
+pub fn live_cells(pattern: &Pattern) -> Vec<(u16, u16)> {
+    let mut cells: Vec<(u16, u16)> = pattern
+        .iter()
+        .filter(|(_, alive)| **alive)
+        .map(|(position, _)| *position)
+        .collect();
+    cells.sort_by_key(|position| (position.1, position.0));
+    return cells;
+}
+
+/// Width and height of the box from `(0, 0)` to the furthest live cell.
+pub fn pattern_size(pattern: &Pattern) -> (usize, usize) {
+    let cells = live_cells(pattern);
+    let width = cells.iter().map(|position| position.0 as usize + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|position| position.1 as usize + 1).max().unwrap_or(0);
+    return (width, height);
+}
+
+/// Shifts signed coordinates so the bounding box starts at `(0, 0)`.
+pub fn normalize(cells: Vec<(i64, i64)>) -> Result<Pattern, PatternError> {
+    let min_x = cells.iter().map(|cell| cell.0).min().unwrap_or(0);
+    let min_y = cells.iter().map(|cell| cell.1).min().unwrap_or(0);
+    let mut pattern = Pattern::new();
+    for (x, y) in cells {
+        let x = x.checked_sub(min_x).and_then(|x| u16::try_from(x).ok());
+        let y = y.checked_sub(min_y).and_then(|y| u16::try_from(y).ok());
+        let (Some(x), Some(y)) = (x, y) else {
+            return Err(PatternError::TooLarge);
+        };
+        pattern.insert((x, y), true);
+    }
+    return Ok(pattern);
+}
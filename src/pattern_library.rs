@@ -0,0 +1,123 @@
+use crate::pattern_file::Pattern;
+use crate::rle::read_rle;
+
+/// A named pattern that ships with the game, kept as RLE so new ones can be pasted
+/// straight from LifeWiki.
+pub struct LibraryPattern {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub rle: &'static str,
+}
+
+impl LibraryPattern {
+    pub fn pattern(&self) -> Pattern {
+        return read_rle(self.rle).expect("library patterns are valid RLE").0;
+    }
+}
+
+pub const PATTERN_LIBRARY: [LibraryPattern; 20] = [
+    LibraryPattern {
+        name: "Glider",
+        description: "smallest spaceship, moves one cell diagonally every 4 generations",
+        rle: "bo$2bo$3o!",
+    },
+    LibraryPattern {
+        name: "LWSS",
+        description: "lightweight spaceship, moves 2 cells every 4 generations",
+        rle: "bo2bo$o$o3bo$4o!",
+    },
+    LibraryPattern {
+        name: "MWSS",
+        description: "middleweight spaceship",
+        rle: "3bo$bo3bo$o$o4bo$5o!",
+    },
+    LibraryPattern {
+        name: "HWSS",
+        description: "heavyweight spaceship",
+        rle: "3b2o$bo4bo$o$o5bo$6o!",
+    },
+    LibraryPattern {
+        name: "Gosper glider gun",
+        description: "fires a glider every 30 generations",
+        rle: "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\
+              10bo5bo7bo$11bo3bo$12b2o!",
+    },
+    LibraryPattern {
+        name: "Pulsar",
+        description: "period 3 oscillator",
+        rle: "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+              o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    },
+    LibraryPattern {
+        name: "Pentadecathlon",
+        description: "period 15 oscillator",
+        rle: "2bo4bo$2ob4ob2o$2bo4bo!",
+    },
+    LibraryPattern {
+        name: "Blinker",
+        description: "period 2 oscillator",
+        rle: "3o!",
+    },
+    LibraryPattern {
+        name: "Toad",
+        description: "period 2 oscillator",
+        rle: "b3o$3o!",
+    },
+    LibraryPattern {
+        name: "Beacon",
+        description: "period 2 oscillator",
+        rle: "2o$2o$2b2o$2b2o!",
+    },
+    LibraryPattern {
+        name: "R-pentomino",
+        description: "methuselah, settles after 1103 generations",
+        rle: "b2o$2o$bo!",
+    },
+    LibraryPattern {
+        name: "Acorn",
+        description: "methuselah, settles after 5206 generations",
+        rle: "bo$3bo$2o2b3o!",
+    },
+    LibraryPattern {
+        name: "Diehard",
+        description: "vanishes after 130 generations",
+        rle: "6bo$2o$bo3b3o!",
+    },
+    LibraryPattern {
+        name: "Block",
+        description: "still life",
+        rle: "2o$2o!",
+    },
+    LibraryPattern {
+        name: "Beehive",
+        description: "still life",
+        rle: "b2o$o2bo$b2o!",
+    },
+    LibraryPattern {
+        name: "Loaf",
+        description: "still life",
+        rle: "b2o$o2bo$bobo$2bo!",
+    },
+    LibraryPattern {
+        name: "Boat",
+        description: "still life",
+        rle: "2o$obo$bo!",
+    },
+    LibraryPattern {
+        name: "Blinker box",
+        description: "the original editor test pattern",
+        rle: "5o$o3bo$b3o!",
+    },
+    LibraryPattern {
+        name: "Cloverleaf",
+        description: "the original editor test pattern",
+        rle: "3bobo$2bo2b3o$2bo5bo$b2o3bobo$bo4b2o$bo$o3bo$o$4bo2$b2obo$obo5bo$o3bo3bo$\
+              b3ob3o$3bobo!",
+    },
+    LibraryPattern {
+        name: "Chaos cloverleaf",
+        description: "the cloverleaf with four extra cells",
+        rle: "3bobo$2bo2b3o$2bo5bo$b2o3bobo$bo4b2obo$bo$o3bo$o5bo$4bob2o2$b2obo$obo5bo$\
+              o3bo3bo$b3ob3o$3bobo!",
+    },
+];
@@ -0,0 +1,58 @@
+use crate::pattern_file::{normalize, pattern_size, Pattern, PatternError, PatternInfo};
+
+/// Reads the plaintext `.cells` format, `!` comment lines (`!Name:` gives the name) then
+/// one row per line with `.` for dead and `O` for alive cells.
+pub fn read_plaintext(contents: &str) -> Result<(Pattern, PatternInfo), PatternError> {
+    let mut info = PatternInfo::default();
+    let mut cells = Vec::new();
+    let mut y = 0_i64;
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim_end();
+        if let Some(comment) = line.strip_prefix('!') {
+            match comment.strip_prefix("Name:") {
+                Some(name) => info.name = Some(name.trim().to_string()),
+                None => info.comments.push(comment.trim().to_string()),
+            }
+            continue;
+        }
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                'O' | '*' => cells.push((x as i64, y)),
+                '.' => {}
+                _ => {
+                    return Err(PatternError::Parse {
+                        line: index + 1,
+                        message: format!("unexpected '{}' in a cell row", c),
+                    })
+                }
+            }
+        }
+        y += 1;
+    }
+    return Ok((normalize(cells)?, info));
+}
+
+pub fn write_plaintext(pattern: &Pattern, info: &PatternInfo) -> String {
+    let mut contents = String::new();
+    if let Some(name) = &info.name {
+        contents.push_str(&format!("!Name: {}\n", name));
+    }
+    for comment in info.comments.iter() {
+        contents.push_str(&format!("!{}\n", comment));
+    }
+
+    let (width, height) = pattern_size(pattern);
+    for y in 0..height {
+        let row: String = (0..width)
+            .map(|x| {
+                let alive = *pattern.get(&(x as u16, y as u16)).unwrap_or(&false);
+                if alive { 'O' } else { '.' }
+            })
+            .collect();
+        // Empty rows keep a single `.` so readers that skip blank lines stay in step.
+        let trimmed = row.trim_end_matches('.');
+        contents.push_str(if trimmed.is_empty() { "." } else { trimmed });
+        contents.push('\n');
+    }
+    return contents;
+}
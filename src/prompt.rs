@@ -0,0 +1,35 @@
+use crossterm::event::KeyCode;
+
+pub enum PromptResult {
+    Editing,
+    Submitted(String),
+    Cancelled,
+}
+
+/// A single line of text typed under the screen, like a file name.
+pub struct TextPrompt {
+    pub label: String,
+    pub input: String,
+}
+
+impl TextPrompt {
+    pub fn new(label: &str) -> TextPrompt {
+        return TextPrompt {
+            label: label.to_string(),
+            input: String::new(),
+        };
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> PromptResult {
+        match code {
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter => return PromptResult::Submitted(self.input.trim().to_string()),
+            KeyCode::Esc => return PromptResult::Cancelled,
+            _ => (),
+        }
+        return PromptResult::Editing;
+    }
+}
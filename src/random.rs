@@ -0,0 +1,24 @@
+/// A small seeded SplitMix64 generator, so the same seed always gives the same soup.
+pub struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        return SeededRandom { state: seed };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    /// `true` with the given probability, `density` is clamped to `0.0..=1.0`.
+    pub fn chance(&mut self, density: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        return unit < density.clamp(0.0, 1.0);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::pattern_file::{normalize, pattern_size, Pattern, PatternError, PatternInfo};
+use crate::rule::Rule;
+
+/// Longest line written, the limit the RLE format asks for.
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Furthest a run can move the position, one past the last cell a pattern can hold.
+const MAX_POSITION: i64 = u16::MAX as i64 + 1;
+
+/// Reads the Run Length Encoded format: `#N`, `#O` and `#C` comment lines, an optional
+/// `x = 3, y = 3, rule = B3/S23` header, then runs of `b` (dead), `o` (alive) and `$`
+/// (end of row) up to a closing `!`.
+///
+/// Multi-state Generations letters are read as `A` alive and everything else dead.
+pub fn read_rle(contents: &str) -> Result<(Pattern, PatternInfo), PatternError> {
+    let mut info = PatternInfo::default();
+    let mut cells: Vec<(i64, i64)> = Vec::new();
+    let mut position = (0_i64, 0_i64);
+    let mut run_count: Option<i64> = None;
+    let mut seen_header = false;
+
+    'lines: for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let parse_error = |message: String| PatternError::Parse { line: index + 1, message };
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut chars = comment.chars();
+            let kind = chars.next();
+            let text = chars.as_str().trim().to_string();
+            match kind {
+                Some('N') => info.name = Some(text),
+                Some('O') => info.author = Some(text),
+                Some('C' | 'c') => info.comments.push(text),
+                // `#P`, `#R` and friends position the pattern, which is centred anyway.
+                _ => {}
+            }
+            continue;
+        }
+        if !seen_header && cells.is_empty() && line.starts_with('x') {
+            seen_header = true;
+            info.rule = read_header(line).map_err(parse_error)?;
+            continue;
+        }
+
+        for c in line.chars() {
+            match c {
+                '0'..='9' => {
+                    let digit = c.to_digit(10).unwrap() as i64;
+                    let count = run_count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|count| count.checked_add(digit))
+                        .filter(|count| *count <= MAX_POSITION)
+                        .ok_or(PatternError::TooLarge)?;
+                    run_count = Some(count);
+                    continue;
+                }
+                'b' | '.' | 'B'..='X' => position.0 = advance(position.0, run_count.unwrap_or(1))?,
+                'o' | 'A' => {
+                    let end = advance(position.0, run_count.unwrap_or(1))?;
+                    cells.extend((position.0..end).map(|x| (x, position.1)));
+                    position.0 = end;
+                }
+                '$' => position = (0, advance(position.1, run_count.unwrap_or(1))?),
+                '!' => break 'lines,
+                c if c.is_whitespace() => {}
+                _ => return Err(parse_error(format!("unexpected '{}' in the cell runs", c))),
+            }
+            run_count = None;
+        }
+    }
+
+    let pattern = normalize(cells)?;
+    return Ok((pattern, info));
+}
+
+/// Moves a row or column position on by a run, as long as it stays where a cell could
+/// still be written after it.
+fn advance(position: i64, run_count: i64) -> Result<i64, PatternError> {
+    return position
+        .checked_add(run_count)
+        .filter(|position| *position <= MAX_POSITION)
+        .ok_or(PatternError::TooLarge);
+}
+
+/// Pulls the rule out of an `x = 3, y = 3, rule = B3/S23` header, the sizes are only a
+/// hint so they're checked but not kept.
+fn read_header(line: &str) -> Result<Option<Rule>, String> {
+    // The rule goes last and may itself contain commas, e.g. `B3/S23:T100,100`.
+    let (sizes, rule_field) = match line.find("rule") {
+        Some(index) => (&line[..index], Some(&line[index..])),
+        None => (line, None),
+    };
+    for field in sizes.split(',').filter(|field| !field.trim().is_empty()) {
+        let Some((key, value)) = field.split_once('=') else {
+            return Err(format!("expected 'key = value' in the header, found '{}'", field.trim()));
+        };
+        let value = value.trim();
+        if matches!(key.trim(), "x" | "y") && value.parse::<u64>().is_err() {
+            return Err(format!("'{}' is not a size", value));
+        }
+    }
+
+    let Some((_, rulestring)) = rule_field.and_then(|field| field.split_once('=')) else {
+        return Ok(None);
+    };
+    // Golly appends the bounded grid after a colon.
+    let rulestring = rulestring.split(':').next().unwrap_or(rulestring);
+    return Rule::parse(rulestring).map(Some).map_err(|error| error.to_string());
+}
+
+/// Writes a pattern as RLE with its comments and a header, wrapping the cell runs at
+/// 70 characters.
+pub fn write_rle(pattern: &Pattern, info: &PatternInfo) -> String {
+    let mut contents = String::new();
+    if let Some(name) = &info.name {
+        contents.push_str(&format!("#N {}\n", name));
+    }
+    if let Some(author) = &info.author {
+        contents.push_str(&format!("#O {}\n", author));
+    }
+    for comment in info.comments.iter() {
+        contents.push_str(&format!("#C {}\n", comment));
+    }
+
+    let (width, height) = pattern_size(pattern);
+    contents.push_str(&format!(
+        "x = {}, y = {}, rule = {}\n",
+        width,
+        height,
+        info.rule.unwrap_or_default()
+    ));
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut cursor_row = 0;
+    for y in 0..height {
+        let mut row_tokens = Vec::new();
+        let mut x = 0;
+        while x < width {
+            let alive = is_alive(pattern, x, y);
+            let mut run = 1;
+            while x + run < width && is_alive(pattern, x + run, y) == alive {
+                run += 1;
+            }
+            // Trailing dead cells are implied by the end of the row.
+            if alive || x + run < width {
+                row_tokens.push(run_token(run, if alive { 'o' } else { 'b' }));
+            }
+            x += run;
+        }
+
+        if !row_tokens.is_empty() {
+            if y > cursor_row {
+                tokens.push(run_token(y - cursor_row, '$'));
+            }
+            tokens.append(&mut row_tokens);
+            cursor_row = y;
+        }
+    }
+    tokens.push("!".to_string());
+
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length + token.len() > MAX_LINE_LENGTH {
+            contents.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        contents.push_str(&token);
+    }
+    contents.push('\n');
+    return contents;
+}
+
+fn is_alive(pattern: &Pattern, x: usize, y: usize) -> bool {
+    return *pattern.get(&(x as u16, y as u16)).unwrap_or(&false);
+}
+
+fn run_token(run: usize, tag: char) -> String {
+    if run == 1 {
+        return tag.to_string();
+    }
+    return format!("{}{}", run, tag);
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::tile::{ALIVE, DEAD};
+
+/// A Life-like birth/survival rule, e.g. Conway's `B3/S23` or HighLife's `B36/S23`, or a
+/// Generations rule such as Brian's Brain `B2/S/C3` where cells that don't survive fade
+/// through `states - 2` dying states before they are dead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+    pub states: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RuleParseError {
+    Empty,
+    MissingSeparator,
+    TooManySections,
+    InvalidNeighborCount(char),
+    DuplicatePrefix(char),
+    BirthOnZero,
+    InvalidStateCount(String),
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleParseError::Empty => write!(f, "rulestring is empty"),
+            RuleParseError::MissingSeparator => {
+                write!(f, "rulestring must contain a '/' between birth and survival")
+            }
+            RuleParseError::TooManySections => write!(f, "rulestring has too many '/' sections"),
+            RuleParseError::InvalidNeighborCount(c) => {
+                write!(f, "'{}' is not a neighbor count between 0 and 8", c)
+            }
+            RuleParseError::DuplicatePrefix(c) => write!(f, "'{}' section appears twice", c),
+            RuleParseError::BirthOnZero => {
+                write!(f, "B0 rules are not supported, cells with no live neighbors are never visited")
+            }
+            RuleParseError::InvalidStateCount(states) => {
+                write!(f, "'{}' is not a state count between 2 and {}", states, u8::MAX)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn conway() -> Rule {
+        return Rule::from_counts(&[3], &[2, 3]);
+    }
+
+    pub fn from_counts(birth_counts: &[usize], survival_counts: &[usize]) -> Rule {
+        let mut birth = [false; 9];
+        let mut survival = [false; 9];
+        for count in birth_counts {
+            birth[*count] = true;
+        }
+        for count in survival_counts {
+            survival[*count] = true;
+        }
+        return Rule { birth, survival, states: 2 };
+    }
+
+    /// Parses either the `B3/S23` notation (sections in any order) or the
+    /// older `23/3` notation, which lists survival counts before birth counts.
+    /// Generations rules add a third section with the number of states, `B2/S/C3` or `/2/3`.
+    pub fn parse(rulestring: &str) -> Result<Rule, RuleParseError> {
+        let rulestring = rulestring.trim();
+        if rulestring.is_empty() {
+            return Err(RuleParseError::Empty);
+        }
+
+        let mut sections: Vec<&str> = rulestring.split('/').collect();
+        if sections.len() < 2 {
+            return Err(RuleParseError::MissingSeparator);
+        }
+        if sections.len() > 3 {
+            return Err(RuleParseError::TooManySections);
+        }
+        let states = if sections.len() == 3 {
+            Rule::parse_states(sections.pop().unwrap())?
+        } else {
+            2
+        };
+
+        let mut birth: Option<[bool; 9]> = None;
+        let mut survival: Option<[bool; 9]> = None;
+        for (index, section) in sections.iter().enumerate() {
+            let prefix = section.chars().next().map(|c| c.to_ascii_uppercase());
+            let (is_birth, digits) = match prefix {
+                Some('B') => (true, &section[1..]),
+                Some('S') => (false, &section[1..]),
+                // Unprefixed sections follow the S/B order of the `23/3` notation.
+                _ => (index == 1, *section),
+            };
+
+            let counts = Rule::parse_counts(digits)?;
+            let slot = if is_birth { &mut birth } else { &mut survival };
+            if slot.is_some() {
+                return Err(RuleParseError::DuplicatePrefix(if is_birth { 'B' } else { 'S' }));
+            }
+            *slot = Some(counts);
+        }
+
+        let birth = birth.unwrap_or([false; 9]);
+        if birth[0] {
+            return Err(RuleParseError::BirthOnZero);
+        }
+
+        return Ok(Rule {
+            birth,
+            survival: survival.unwrap_or([false; 9]),
+            states,
+        });
+    }
+
+    fn parse_states(section: &str) -> Result<u8, RuleParseError> {
+        let digits = section.trim_start_matches(['C', 'c', 'G', 'g']);
+        return match digits.parse::<u8>() {
+            Ok(states) if states >= 2 => Ok(states),
+            _ => Err(RuleParseError::InvalidStateCount(section.to_string())),
+        };
+    }
+
+    fn parse_counts(digits: &str) -> Result<[bool; 9], RuleParseError> {
+        let mut counts = [false; 9];
+        for c in digits.chars() {
+            match c.to_digit(10) {
+                Some(count) if count <= 8 => counts[count as usize] = true,
+                _ => return Err(RuleParseError::InvalidNeighborCount(c)),
+            }
+        }
+        return Ok(counts);
+    }
+
+    pub fn is_born(&self, live_neighbors: usize) -> bool {
+        return live_neighbors < 9 && self.birth[live_neighbors];
+    }
+
+    pub fn survives(&self, live_neighbors: usize) -> bool {
+        return live_neighbors < 9 && self.survival[live_neighbors];
+    }
+
+    /// Whether cells pass through dying states instead of dying straight away.
+    pub fn is_generations(&self) -> bool {
+        return self.states > 2;
+    }
+
+    /// The state of a cell next generation, using the `Tile` numbering of 0 for dead,
+    /// 1 for alive and 2 onwards for dying.
+    pub fn next_state(&self, state: u8, live_neighbors: usize) -> u8 {
+        return match state {
+            DEAD => if self.is_born(live_neighbors) { ALIVE } else { DEAD },
+            ALIVE => {
+                if self.survives(live_neighbors) {
+                    ALIVE
+                } else if self.is_generations() {
+                    ALIVE + 1
+                } else {
+                    DEAD
+                }
+            }
+            dying => if dying + 1 < self.states { dying + 1 } else { DEAD },
+        };
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        return Rule::conway();
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(rulestring: &str) -> Result<Rule, RuleParseError> {
+        return Rule::parse(rulestring);
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for (count, born) in self.birth.iter().enumerate() {
+            if *born {
+                write!(f, "{}", count)?;
+            }
+        }
+        write!(f, "/S")?;
+        for (count, survives) in self.survival.iter().enumerate() {
+            if *survives {
+                write!(f, "{}", count)?;
+            }
+        }
+        if self.is_generations() {
+            write!(f, "/C{}", self.states)?;
+        }
+        return Ok(());
+    }
+}
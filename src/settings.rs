@@ -0,0 +1,176 @@
+use crate::design_world::DesignWorld;
+use crate::view_limits::ViewLimits;
+use crate::world::{World, MAX_FRAME_INTERVAL_MS, MAX_WORLD_SIZE};
+
+/// Longest number a field accepts, keeps sizes well clear of overflowing `usize` maths.
+const MAX_FIELD_DIGITS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsField {
+    WorldWidth,
+    WorldHeight,
+    ChunkWidth,
+    ChunkHeight,
+    FrameIntervalMs,
+    DesignWidth,
+    DesignHeight,
+}
+
+impl SettingsField {
+    pub const ALL: [SettingsField; 7] = [
+        SettingsField::WorldWidth,
+        SettingsField::WorldHeight,
+        SettingsField::ChunkWidth,
+        SettingsField::ChunkHeight,
+        SettingsField::FrameIntervalMs,
+        SettingsField::DesignWidth,
+        SettingsField::DesignHeight,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        return match self {
+            SettingsField::WorldWidth => "World width",
+            SettingsField::WorldHeight => "World height",
+            SettingsField::ChunkWidth => "Chunk width",
+            SettingsField::ChunkHeight => "Chunk height",
+            SettingsField::FrameIntervalMs => "Frame interval (ms)",
+            SettingsField::DesignWidth => "Design map width",
+            SettingsField::DesignHeight => "Design map height",
+        };
+    }
+}
+
+/// The validated values of a `SettingsForm`, ready to be applied.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Settings {
+    pub world_width: usize,
+    pub world_height: usize,
+    pub chunk_width: usize,
+    pub chunk_height: usize,
+    pub frame_interval_ms: usize,
+    pub design_width: u16,
+    pub design_height: u16,
+}
+
+/// The settings screen, one text buffer per `SettingsField` so a half typed number can be
+/// shown and fixed before anything is applied.
+pub struct SettingsForm {
+    pub values: Vec<String>,
+    pub selected: usize,
+    pub error: Option<String>,
+}
+
+impl SettingsForm {
+    /// Starts from the sizes that were asked for, not the ones a small terminal shrank the
+    /// view and design map down to.
+    pub fn new(world: &World, view_limits: &ViewLimits) -> SettingsForm {
+        let values = [
+            world.x_size,
+            world.y_size,
+            view_limits.chunk_x_size,
+            view_limits.chunk_y_size,
+            world.frame_interval_ms,
+            view_limits.design_x_size as usize,
+            view_limits.design_y_size as usize,
+        ];
+        return SettingsForm {
+            values: values.iter().map(|value| value.to_string()).collect(),
+            selected: 0,
+            error: None,
+        };
+    }
+
+    /// Moves the selection up or down, wrapping around at either end.
+    pub fn move_selection(&mut self, offset: i32) {
+        let field_count = SettingsField::ALL.len() as i32;
+        self.selected = (self.selected as i32 + offset).rem_euclid(field_count) as usize;
+    }
+
+    /// Appends a digit to the selected field, anything else is ignored.
+    pub fn push_char(&mut self, c: char) {
+        let value = &mut self.values[self.selected];
+        if c.is_ascii_digit() && value.len() < MAX_FIELD_DIGITS {
+            value.push(c);
+            self.error = None;
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        self.values[self.selected].pop();
+        self.error = None;
+    }
+
+    fn value_of(&self, field: SettingsField) -> Result<usize, String> {
+        let index = SettingsField::ALL.iter().position(|f| *f == field).unwrap();
+        return self.values[index]
+            .parse::<usize>()
+            .map_err(|_| format!("{} needs a number", field.label()));
+    }
+
+    /// A design map side, which has to fit the `u16` positions the map is drawn with.
+    fn design_value_of(&self, field: SettingsField) -> Result<u16, String> {
+        return u16::try_from(self.value_of(field)?)
+            .map_err(|_| format!("{} must be at most {}", field.label(), u16::MAX));
+    }
+
+    pub fn validate(&self) -> Result<Settings, String> {
+        let settings = Settings {
+            world_width: self.value_of(SettingsField::WorldWidth)?,
+            world_height: self.value_of(SettingsField::WorldHeight)?,
+            chunk_width: self.value_of(SettingsField::ChunkWidth)?,
+            chunk_height: self.value_of(SettingsField::ChunkHeight)?,
+            frame_interval_ms: self.value_of(SettingsField::FrameIntervalMs)?,
+            design_width: self.design_value_of(SettingsField::DesignWidth)?,
+            design_height: self.design_value_of(SettingsField::DesignHeight)?,
+        };
+
+        if settings.world_width > MAX_WORLD_SIZE || settings.world_height > MAX_WORLD_SIZE {
+            return Err(format!("World can be at most {}x{}", MAX_WORLD_SIZE, MAX_WORLD_SIZE));
+        }
+        if settings.frame_interval_ms > MAX_FRAME_INTERVAL_MS {
+            return Err(format!("Frame interval can be at most {} ms", MAX_FRAME_INTERVAL_MS));
+        }
+
+        // The design map needs a cell inside its border, and is copied 1:1 into the world.
+        if settings.design_width < 3 || settings.design_height < 3 {
+            return Err("Design map must be at least 3x3".to_string());
+        }
+        if settings.world_width < settings.design_width as usize
+            || settings.world_height < settings.design_height as usize
+        {
+            return Err("World must be at least as large as the design map".to_string());
+        }
+        if settings.chunk_width == 0 || settings.chunk_height == 0 {
+            return Err("Chunk must be at least 1x1".to_string());
+        }
+        if settings.chunk_width > settings.world_width
+            || settings.chunk_height > settings.world_height
+        {
+            return Err("Chunk can't be larger than the world".to_string());
+        }
+        return Ok(settings);
+    }
+
+    /// Validates the form and resizes the world and design map, keeping the error for the
+    /// screen to show when the values don't fit together.
+    pub fn apply(&mut self, world: &mut World, design_world: &mut DesignWorld) -> bool {
+        return match self.validate() {
+            Ok(settings) => {
+                world.resize(
+                    settings.world_width,
+                    settings.world_height,
+                    settings.chunk_width,
+                    settings.chunk_height,
+                );
+                world.frame_interval_ms = settings.frame_interval_ms;
+                design_world.resize(settings.design_width, settings.design_height);
+                self.error = None;
+                true
+            }
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        };
+    }
+}
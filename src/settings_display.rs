@@ -0,0 +1,38 @@
+use std::io::Stdout;
+use crossterm::execute;
+use crossterm::style::Print;
+use crossterm::style::Stylize;
+use crossterm::{
+    cursor,
+    terminal::{
+        Clear,
+        ClearType,
+    },
+};
+use crate::settings::{SettingsField, SettingsForm};
+
+pub struct SettingsDisplay {}
+
+impl SettingsDisplay {
+    pub fn print_settings(stdout: &mut Stdout, form: &SettingsForm) {
+        execute!(stdout, Clear(ClearType::FromCursorUp), cursor::MoveTo(0, 0), Print("")).unwrap();
+        print!(
+            "Settings\n\
+            up,down,tab: select a field\n\
+            0-9,backspace: edit the selected field\n\
+            enter: apply\n\
+            esc: cancel\n\n"
+        );
+        for (index, field) in SettingsField::ALL.iter().enumerate() {
+            let line = format!("{:<20} {}", field.label(), form.values[index]);
+            if index == form.selected {
+                println!("{}", format!("> {}_", line).yellow());
+            } else {
+                println!("  {}", line);
+            }
+        }
+        if let Some(error) = &form.error {
+            println!("\n{}", error.as_str().red());
+        }
+    }
+}
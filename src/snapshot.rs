@@ -0,0 +1,328 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::boundary::BoundaryMode;
+use crate::design_world::DesignWorld;
+use crate::rule::Rule;
+use crate::tile::ALIVE;
+use crate::view_limits::ViewLimits;
+use crate::world::{StorageKind, World, MAX_FRAME_INTERVAL_MS, MAX_WORLD_SIZE};
+
+const SNAPSHOT_MAGIC: &str = "conways-game-of-life-snapshot";
+/// Bumped whenever the layout changes, older versions keep loading where they can.
+const SNAPSHOT_VERSION: u32 = 3;
+
+/// Everything needed to pick a run back up: the world, where it was being viewed from and
+/// the design map it was started from.
+///
+/// Written as one `key values` line per setting followed by `cells`, `dying` and `marks`
+/// sections, so a snapshot can be read and diffed by hand.
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    pub x_size: usize,
+    pub y_size: usize,
+    pub chunk_x_size: usize,
+    pub chunk_y_size: usize,
+    pub frame_interval_ms: usize,
+    pub rule: Rule,
+    pub boundary_mode: BoundaryMode,
+    pub storage_kind: StorageKind,
+    pub current_generation: u128,
+    pub viewport_origin: (i64, i64),
+    pub live_cells: Vec<(i64, i64)>,
+    /// Cells fading out under a Generations rule, with their `Tile` state.
+    pub dying_cells: Vec<((i64, i64), u8)>,
+    pub design_size: (u16, u16),
+    pub design_position: (u16, u16),
+    pub design_marks: Vec<(u16, u16)>,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    NotASnapshot,
+    UnsupportedVersion(u32),
+    Parse { line: usize, message: String },
+    Missing(&'static str),
+    Invalid(&'static str),
+    TooLarge { what: &'static str, maximum: usize },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "{}", error),
+            SnapshotError::NotASnapshot => write!(f, "not a snapshot file"),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "snapshot version {} is newer than this build understands ({})",
+                version, SNAPSHOT_VERSION
+            ),
+            SnapshotError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            SnapshotError::Missing(key) => write!(f, "snapshot has no '{}' line", key),
+            SnapshotError::Invalid(message) => write!(f, "{}", message),
+            SnapshotError::TooLarge { what, maximum } => write!(f, "{} can be at most {}", what, maximum),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(error: std::io::Error) -> SnapshotError {
+        return SnapshotError::Io(error);
+    }
+}
+
+impl Snapshot {
+    /// Takes the view and design map sizes from `view_limits`, the ones asked for rather than
+    /// what fits the terminal, and the design marks set aside along with the visible ones.
+    pub fn capture(world: &World, design_world: &DesignWorld, view_limits: &ViewLimits) -> Snapshot {
+        let mut live_cells = world.live_cells();
+        live_cells.sort_by_key(|position| (position.1, position.0));
+        let mut dying_cells = world.dying_cells();
+        dying_cells.sort_by_key(|(position, _)| (position.1, position.0));
+        let mut design_marks: Vec<(u16, u16)> = design_world
+            .all_marks()
+            .filter(|(_, marked)| **marked)
+            .map(|(position, _)| *position)
+            .collect();
+        design_marks.sort_by_key(|position| (position.1, position.0));
+
+        return Snapshot {
+            x_size: world.x_size,
+            y_size: world.y_size,
+            chunk_x_size: view_limits.chunk_x_size,
+            chunk_y_size: view_limits.chunk_y_size,
+            frame_interval_ms: world.frame_interval_ms,
+            rule: world.rule,
+            boundary_mode: world.boundary_mode,
+            storage_kind: world.storage_kind,
+            current_generation: world.current_generation,
+            viewport_origin: world.viewport_origin,
+            live_cells,
+            dying_cells,
+            design_size: (view_limits.design_x_size, view_limits.design_y_size),
+            design_position: design_world.current_position,
+            design_marks,
+        };
+    }
+
+    pub fn restore(&self, world: &mut World, design_world: &mut DesignWorld) {
+        world.x_size = self.x_size;
+        world.y_size = self.y_size;
+        world.chunk_x_size = self.chunk_x_size;
+        world.chunk_y_size = self.chunk_y_size;
+        world.frame_interval_ms = self.frame_interval_ms;
+        world.rule = self.rule;
+        world.boundary_mode = self.boundary_mode;
+        world.storage_kind = self.storage_kind;
+        world.replace_live_cells(self.live_cells.clone());
+        for (position, state) in self.dying_cells.iter() {
+            world.insert_dying_cell(*position, *state);
+        }
+        world.current_generation = self.current_generation;
+        world.viewport_origin = self.viewport_origin;
+
+        *design_world = DesignWorld::new(self.design_size.0, self.design_size.1);
+        for position in self.design_marks.iter() {
+            if design_world.is_editable(*position) {
+                design_world.marked_positions.insert(*position, true);
+            }
+        }
+        if design_world.is_editable(self.design_position) {
+            design_world.current_position = self.design_position;
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", SNAPSHOT_MAGIC, SNAPSHOT_VERSION);
+        text.push_str(&format!("world {} {}\n", self.x_size, self.y_size));
+        text.push_str(&format!("chunk {} {}\n", self.chunk_x_size, self.chunk_y_size));
+        text.push_str(&format!("interval {}\n", self.frame_interval_ms));
+        text.push_str(&format!("rule {}\n", self.rule));
+        text.push_str(&format!("boundary {}\n", self.boundary_mode.name()));
+        text.push_str(&format!("storage {}\n", self.storage_kind.name()));
+        text.push_str(&format!("generation {}\n", self.current_generation));
+        text.push_str(&format!(
+            "viewport {} {}\n",
+            self.viewport_origin.0, self.viewport_origin.1
+        ));
+        text.push_str(&format!(
+            "design {} {} {} {}\n",
+            self.design_size.0, self.design_size.1, self.design_position.0, self.design_position.1
+        ));
+        text.push_str(&format!("cells {}\n", self.live_cells.len()));
+        for (x, y) in self.live_cells.iter() {
+            text.push_str(&format!("{} {}\n", x, y));
+        }
+        text.push_str(&format!("dying {}\n", self.dying_cells.len()));
+        for ((x, y), state) in self.dying_cells.iter() {
+            text.push_str(&format!("{} {} {}\n", x, y, state));
+        }
+        text.push_str(&format!("marks {}\n", self.design_marks.len()));
+        for (x, y) in self.design_marks.iter() {
+            text.push_str(&format!("{} {}\n", x, y));
+        }
+        return text;
+    }
+
+    pub fn parse(text: &str) -> Result<Snapshot, SnapshotError> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let (_, first_line) = lines.next().ok_or(SnapshotError::NotASnapshot)?;
+        let version = match first_line.split_whitespace().collect::<Vec<&str>>()[..] {
+            [SNAPSHOT_MAGIC, version] => version.parse::<u32>().map_err(|_| SnapshotError::NotASnapshot)?,
+            _ => return Err(SnapshotError::NotASnapshot),
+        };
+        if version > SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut snapshot = Snapshot::empty();
+        let mut seen_keys: Vec<&str> = Vec::new();
+        while let Some((index, line)) = lines.next() {
+            let parse_error = |message: String| SnapshotError::Parse { line: index + 1, message };
+            let (key, values) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            let values = values.trim();
+            match key {
+                "world" => (snapshot.x_size, snapshot.y_size) = parse_pair(values).map_err(parse_error)?,
+                "chunk" => {
+                    (snapshot.chunk_x_size, snapshot.chunk_y_size) = parse_pair(values).map_err(parse_error)?
+                }
+                "interval" => snapshot.frame_interval_ms = parse_value(values).map_err(parse_error)?,
+                "rule" => snapshot.rule = Rule::parse(values).map_err(|error| parse_error(error.to_string()))?,
+                "boundary" => {
+                    snapshot.boundary_mode = BoundaryMode::from_name(values)
+                        .ok_or_else(|| parse_error(format!("unknown boundary '{}'", values)))?
+                }
+                "storage" => {
+                    snapshot.storage_kind = StorageKind::from_name(values)
+                        .ok_or_else(|| parse_error(format!("unknown storage '{}'", values)))?
+                }
+                "generation" => snapshot.current_generation = parse_value(values).map_err(parse_error)?,
+                "viewport" => snapshot.viewport_origin = parse_pair(values).map_err(parse_error)?,
+                "design" => {
+                    let numbers: Vec<u16> = values
+                        .split_whitespace()
+                        .map(parse_value)
+                        .collect::<Result<Vec<u16>, String>>()
+                        .map_err(parse_error)?;
+                    let [max_x, max_y, x, y] = numbers[..] else {
+                        return Err(parse_error("expected 'design width height x y'".to_string()));
+                    };
+                    snapshot.design_size = (max_x, max_y);
+                    snapshot.design_position = (x, y);
+                }
+                "cells" | "dying" | "marks" => {
+                    let count: usize = parse_value(values).map_err(parse_error)?;
+                    for _ in 0..count {
+                        let Some((index, line)) = lines.next() else {
+                            return Err(parse_error(format!("expected {} '{}' lines", count, key)));
+                        };
+                        let parse_error = |message: String| SnapshotError::Parse { line: index + 1, message };
+                        match key {
+                            "cells" => snapshot.live_cells.push(parse_pair(line).map_err(parse_error)?),
+                            "dying" => snapshot.dying_cells.push(parse_dying_cell(line).map_err(parse_error)?),
+                            _ => snapshot.design_marks.push(parse_pair(line).map_err(parse_error)?),
+                        }
+                    }
+                }
+                // Keys from newer minor additions are skipped rather than rejected.
+                _ => continue,
+            }
+            seen_keys.push(key);
+        }
+
+        for key in ["world", "chunk", "rule", "design", "cells"] {
+            if !seen_keys.contains(&key) {
+                return Err(SnapshotError::Missing(key));
+            }
+        }
+        // Version 1 kept the view as a chunk index rather than a cell.
+        if version < 2 {
+            snapshot.viewport_origin = (
+                snapshot.viewport_origin.0 * snapshot.chunk_x_size as i64,
+                snapshot.viewport_origin.1 * snapshot.chunk_y_size as i64,
+            );
+        }
+        snapshot.validate()?;
+        return Ok(snapshot);
+    }
+
+    fn empty() -> Snapshot {
+        return Snapshot {
+            x_size: 0,
+            y_size: 0,
+            chunk_x_size: 0,
+            chunk_y_size: 0,
+            frame_interval_ms: 36,
+            rule: Rule::conway(),
+            boundary_mode: BoundaryMode::Dead,
+            storage_kind: StorageKind::Tiles,
+            current_generation: 1,
+            viewport_origin: (0, 0),
+            live_cells: Vec::new(),
+            dying_cells: Vec::new(),
+            design_size: (0, 0),
+            design_position: (1, 1),
+            design_marks: Vec::new(),
+        };
+    }
+
+    /// The same size rules the settings screen enforces, so a hand edited snapshot can't
+    /// leave the world in a state it couldn't otherwise reach.
+    fn validate(&self) -> Result<(), SnapshotError> {
+        if self.x_size > MAX_WORLD_SIZE || self.y_size > MAX_WORLD_SIZE {
+            return Err(SnapshotError::TooLarge { what: "world width and height", maximum: MAX_WORLD_SIZE });
+        }
+        if self.frame_interval_ms > MAX_FRAME_INTERVAL_MS {
+            return Err(SnapshotError::TooLarge { what: "frame interval in ms", maximum: MAX_FRAME_INTERVAL_MS });
+        }
+        if self.design_size.0 < 3 || self.design_size.1 < 3 {
+            return Err(SnapshotError::Invalid("design map must be at least 3x3"));
+        }
+        if self.x_size < self.design_size.0 as usize || self.y_size < self.design_size.1 as usize {
+            return Err(SnapshotError::Invalid("world must be at least as large as the design map"));
+        }
+        if self.chunk_x_size == 0
+            || self.chunk_y_size == 0
+            || self.chunk_x_size > self.x_size
+            || self.chunk_y_size > self.y_size
+        {
+            return Err(SnapshotError::Invalid("chunk must fit inside the world"));
+        }
+        if self.dying_cells.iter().any(|(_, state)| *state <= ALIVE || *state >= self.rule.states) {
+            return Err(SnapshotError::Invalid("dying cell states must be dying states of the rule"));
+        }
+        return Ok(());
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        fs::write(path, self.to_text())?;
+        return Ok(());
+    }
+
+    pub fn load(path: &Path) -> Result<Snapshot, SnapshotError> {
+        return Snapshot::parse(&fs::read_to_string(path)?);
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    return value.trim().parse::<T>().map_err(|_| format!("'{}' is not a valid number", value.trim()));
+}
+
+fn parse_pair<T: std::str::FromStr>(values: &str) -> Result<(T, T), String> {
+    let mut parts = values.split_whitespace();
+    let (Some(first), Some(second), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!("expected two numbers, found '{}'", values.trim()));
+    };
+    return Ok((parse_value(first)?, parse_value(second)?));
+}
+
+/// Reads a `x y state` line of the `dying` section.
+fn parse_dying_cell(values: &str) -> Result<((i64, i64), u8), String> {
+    let Some((position, state)) = values.trim().rsplit_once(' ') else {
+        return Err(format!("expected 'x y state', found '{}'", values.trim()));
+    };
+    return Ok((parse_pair(position)?, parse_value(state)?));
+}
@@ -0,0 +1,87 @@
+use std::collections::{HashMap, HashSet};
+use crate::rule::Rule;
+use crate::tile::{ALIVE, DEAD};
+
+/// An unbounded plane that only stores the coordinates of live cells, so patterns can
+/// grow in any direction for as long as they like without a preallocated grid.
+pub struct SparseWorld {
+    pub alive_tile_keys: HashSet<(i64, i64)>,
+    /// The `Tile` state of every cell fading out under a Generations rule.
+    pub dying_tile_keys: HashMap<(i64, i64), u8>,
+}
+
+impl SparseWorld {
+    pub fn new() -> SparseWorld {
+        return SparseWorld {
+            alive_tile_keys: HashSet::new(),
+            dying_tile_keys: HashMap::new(),
+        };
+    }
+
+    pub fn insert_position(&mut self, key: (i64, i64)) {
+        self.alive_tile_keys.insert(key);
+    }
+
+    pub fn is_alive(&self, key: (i64, i64)) -> bool {
+        return self.alive_tile_keys.contains(&key);
+    }
+
+    pub fn cell_state(&self, key: (i64, i64)) -> u8 {
+        if self.is_alive(key) {
+            return ALIVE;
+        }
+        return *self.dying_tile_keys.get(&key).unwrap_or(&DEAD);
+    }
+
+    pub fn population(&self) -> usize {
+        return self.alive_tile_keys.len();
+    }
+
+    pub fn handle_generation(&mut self, rule: &Rule) {
+        let mut live_neighbors: HashMap<(i64, i64), usize> = HashMap::new();
+        for key in self.alive_tile_keys.iter() {
+            for y_offset in -1..=1 {
+                for x_offset in -1..=1 {
+                    if x_offset == 0 && y_offset == 0 {
+                        continue;
+                    }
+                    let neighbor_key = (
+                        key.0.wrapping_add(x_offset),
+                        key.1.wrapping_add(y_offset),
+                    );
+                    *live_neighbors.entry(neighbor_key).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut next_alive_tile_keys = HashSet::new();
+        let mut next_dying_tile_keys = HashMap::new();
+        for key in self.alive_tile_keys.iter() {
+            let next_state = rule.next_state(ALIVE, *live_neighbors.get(key).unwrap_or(&0));
+            if next_state == ALIVE {
+                next_alive_tile_keys.insert(*key);
+            } else if next_state != DEAD {
+                next_dying_tile_keys.insert(*key, next_state);
+            }
+        }
+        for (key, state) in self.dying_tile_keys.iter() {
+            let next_state = rule.next_state(*state, 0);
+            if next_state != DEAD {
+                next_dying_tile_keys.insert(*key, next_state);
+            }
+        }
+        for (key, count) in live_neighbors {
+            if self.cell_state(key) == DEAD && rule.is_born(count) {
+                next_alive_tile_keys.insert(key);
+            }
+        }
+        self.alive_tile_keys = next_alive_tile_keys;
+        self.dying_tile_keys = next_dying_tile_keys;
+    }
+}
+
+impl Default for SparseWorld {
+    fn default() -> SparseWorld {
+        return SparseWorld::new();
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+pub mod test_bit_packed {
+    use test_case::test_case;
+    use crate::boundary::BoundaryMode;
+    use crate::random::SeededRandom;
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+
+    fn soup(x_size: usize, y_size: usize, seed: u64) -> Vec<(i64, i64)> {
+        let mut random = SeededRandom::new(seed);
+        let mut cells = Vec::new();
+        for y in 0..y_size {
+            for x in 0..x_size {
+                if random.chance(1.0 / 3.0) {
+                    cells.push((x as i64, y as i64));
+                }
+            }
+        }
+        return cells;
+    }
+
+    #[test_case(70, 33, BoundaryMode::Dead, "B3/S23" ; "dead edges")]
+    #[test_case(64, 20, BoundaryMode::Torus, "B3/S23" ; "torus on a word boundary")]
+    #[test_case(130, 17, BoundaryMode::Torus, "B36/S23" ; "torus highlife")]
+    #[test_case(67, 31, BoundaryMode::KleinBottle, "B3/S23" ; "klein bottle")]
+    #[test_case(45, 45, BoundaryMode::CrossSurface, "B3678/S34678" ; "cross surface day and night")]
+    fn test_matches_tiles(x_size: usize, y_size: usize, boundary_mode: BoundaryMode, rulestring: &str) {
+        let rule = Rule::parse(rulestring).unwrap();
+        let mut tiles = World::new(x_size, y_size, x_size, y_size, 0, 0, rule, StorageKind::Tiles);
+        let mut bits = World::new(x_size, y_size, x_size, y_size, 0, 0, rule, StorageKind::BitPacked);
+        tiles.boundary_mode = boundary_mode;
+        bits.boundary_mode = boundary_mode;
+        for key in soup(x_size, y_size, 7) {
+            tiles.insert_live_cell(key);
+            bits.insert_live_cell(key);
+        }
+
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for generation in 0..40 {
+            tiles.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            bits.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+
+            let mut tiles_cells = tiles.live_cells();
+            let mut bits_cells = bits.live_cells();
+            tiles_cells.sort();
+            bits_cells.sort();
+            assert_eq!(tiles_cells, bits_cells, "diverged at generation {}", generation);
+        }
+    }
+}
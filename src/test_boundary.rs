@@ -0,0 +1,41 @@
+#[cfg(test)]
+pub mod test_boundaries {
+    use test_case::test_case;
+    use crate::boundary::BoundaryMode;
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+
+    #[test_case(BoundaryMode::Dead, (-1, 2), None ; "dead edge")]
+    #[test_case(BoundaryMode::Torus, (-1, 2), Some((9, 2)) ; "torus left edge")]
+    #[test_case(BoundaryMode::Torus, (3, 5), Some((3, 0)) ; "torus bottom edge")]
+    #[test_case(BoundaryMode::KleinBottle, (-1, 2), Some((9, 2)) ; "klein left edge")]
+    #[test_case(BoundaryMode::KleinBottle, (3, 5), Some((6, 0)) ; "klein bottom edge mirrors x")]
+    #[test_case(BoundaryMode::CrossSurface, (-1, 1), Some((9, 3)) ; "cross left edge mirrors y")]
+    #[test_case(BoundaryMode::CrossSurface, (3, -1), Some((6, 4)) ; "cross top edge mirrors x")]
+    #[test_case(BoundaryMode::Torus, (4, 4), Some((4, 4)) ; "in range is untouched")]
+    fn test_resolve(mode: BoundaryMode, position: (i64, i64), expected: Option<(usize, usize)>) {
+        assert_eq!(mode.resolve(position.0, position.1, 10, 5), expected);
+    }
+
+    #[test]
+    fn test_glider_survives_a_lap_of_the_torus() {
+        let mut world = World::new(8, 8, 8, 8, 0, 0, Rule::conway(), StorageKind::Tiles);
+        world.boundary_mode = BoundaryMode::Torus;
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        for position in glider {
+            World::insert_position(&mut world.tiles, &mut world.alive_tile_keys, position);
+        }
+
+        // A glider moves one cell diagonally every four generations.
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for _ in 0..(4 * 8) {
+            world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+
+        assert_eq!(world.alive_tile_keys.len(), 5);
+        for position in glider {
+            assert!(world.tiles[&position].is_alive());
+        }
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+pub mod test_parallel_generation {
+    use test_case::test_case;
+    use crate::boundary::BoundaryMode;
+    use crate::chunk_stripe::ChunkStripe;
+    use crate::random::SeededRandom;
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+
+    #[test_case(10, 3, vec![4, 4, 2] ; "short last stripe")]
+    #[test_case(12, 4, vec![3, 3, 3, 3] ; "even split")]
+    #[test_case(2, 8, vec![1, 1] ; "more threads than rows")]
+    fn test_split_by_stripe_count(y_size: usize, stripe_count: usize, expected: Vec<usize>) {
+        let stripes = ChunkStripe::split(5, y_size, stripe_count);
+        assert_eq!(stripes.iter().map(|stripe| stripe.rows).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_sparse_and_small_worlds_step_serially() {
+        let mut world = World::new(250, 250, 75, 50, 0, 0, Rule::conway(), StorageKind::Tiles);
+        world.worker_threads = 8;
+        for cell in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            world.insert_live_cell(cell);
+        }
+        assert!(!world.uses_parallel_generation());
+
+        let mut small = World::new(20, 20, 20, 20, 0, 0, Rule::conway(), StorageKind::Tiles);
+        small.worker_threads = 8;
+        for x in 0..20 {
+            small.insert_live_cell((x, 10));
+        }
+        assert!(!small.uses_parallel_generation());
+    }
+
+    #[test_case(80, 60, BoundaryMode::Dead, 4 ; "dead edges")]
+    #[test_case(90, 50, BoundaryMode::Torus, 3 ; "torus with a short last stripe")]
+    #[test_case(66, 70, BoundaryMode::KleinBottle, 8 ; "klein bottle")]
+    #[test_case(70, 66, BoundaryMode::CrossSurface, 2 ; "two stripe cross surface")]
+    fn test_matches_serial(
+        x_size: usize,
+        y_size: usize,
+        boundary_mode: BoundaryMode,
+        worker_threads: usize,
+    ) {
+        let mut serial = World::new(x_size, y_size, x_size, y_size, 0, 0, Rule::conway(), StorageKind::Tiles);
+        let mut parallel = World::new(x_size, y_size, x_size, y_size, 0, 0, Rule::conway(), StorageKind::Tiles);
+        serial.boundary_mode = boundary_mode;
+        parallel.boundary_mode = boundary_mode;
+        parallel.worker_threads = worker_threads;
+
+        let mut random = SeededRandom::new(11);
+        for y in 0..y_size {
+            for x in 0..x_size {
+                if random.chance(0.5) {
+                    serial.insert_live_cell((x as i64, y as i64));
+                    parallel.insert_live_cell((x as i64, y as i64));
+                }
+            }
+        }
+
+        assert!(parallel.uses_parallel_generation());
+
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for generation in 0..40 {
+            serial.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            parallel.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+
+            let mut serial_cells = serial.live_cells();
+            let mut parallel_cells = parallel.live_cells();
+            serial_cells.sort();
+            parallel_cells.sort();
+            assert_eq!(serial_cells, parallel_cells, "diverged at generation {}", generation);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+pub mod test_command_line {
+    use test_case::test_case;
+    use crate::boundary::BoundaryMode;
+    use crate::cli_args::{CliArgs, CliError};
+    use crate::rule::Rule;
+    use crate::world::StorageKind;
+
+    fn parse(arguments: &str) -> Result<CliArgs, CliError> {
+        return CliArgs::parse(arguments.split_whitespace().map(String::from));
+    }
+
+    #[test]
+    fn test_no_arguments_gives_the_defaults() {
+        assert_eq!(parse(""), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn test_every_flag() {
+        let cli_args = parse(
+            "--width 400 --height=300 --chunk-width 80 --chunk-height 40 --interval-ms 0 \
+             --rule B36/S23 --boundary klein --storage bits --threads 2 --pattern glider.lif \
+             --seed 42 --pan-step 3",
+        )
+        .unwrap();
+
+        assert_eq!((cli_args.width, cli_args.height), (400, 300));
+        assert_eq!((cli_args.chunk_width, cli_args.chunk_height), (80, 40));
+        assert_eq!(cli_args.interval_ms, 0);
+        assert_eq!(cli_args.rule, Some(Rule::from_counts(&[3, 6], &[2, 3])));
+        assert_eq!(cli_args.boundary_mode, BoundaryMode::KleinBottle);
+        assert_eq!(cli_args.storage_kind, StorageKind::BitPacked);
+        assert_eq!(cli_args.worker_threads, Some(2));
+        assert_eq!(cli_args.pattern.unwrap().to_str(), Some("glider.lif"));
+        assert_eq!(cli_args.seed, Some(42));
+        assert_eq!(cli_args.pan_step, 3);
+    }
+
+    #[test_case("--help" ; "long")]
+    #[test_case("--width 1 -h" ; "short skips validation")]
+    fn test_help(arguments: &str) {
+        assert!(parse(arguments).unwrap().show_help);
+    }
+
+    #[test_case("--depth 3", CliError::UnknownArgument("--depth".to_string()) ; "unknown flag")]
+    #[test_case("250", CliError::UnknownArgument("250".to_string()) ; "bare value")]
+    #[test_case("--width", CliError::MissingValue("--width".to_string()) ; "missing value")]
+    #[test_case(
+        "--height -5",
+        CliError::InvalidNumber { flag: "--height".to_string(), value: "-5".to_string() }
+        ; "negative number"
+    )]
+    #[test_case("--width 2", CliError::TooSmall { flag: "--width".to_string(), minimum: 3 } ; "tiny world")]
+    #[test_case("--height 5000", CliError::TooLarge { flag: "--height".to_string(), maximum: 4096 } ; "huge world")]
+    #[test_case(
+        "--width 99999999999 --chunk-width 10",
+        CliError::TooLarge { flag: "--width".to_string(), maximum: 4096 }
+        ; "world past the maximum before the chunk check"
+    )]
+    #[test_case("--interval-ms 2001", CliError::TooLarge { flag: "--interval-ms".to_string(), maximum: 2000 } ; "slow interval")]
+    #[test_case("--pan-step 5000", CliError::TooLarge { flag: "--pan-step".to_string(), maximum: 4096 } ; "huge pan step")]
+    #[test_case("--threads 0", CliError::TooSmall { flag: "--threads".to_string(), minimum: 1 } ; "no threads")]
+    #[test_case("--pan-step 0", CliError::TooSmall { flag: "--pan-step".to_string(), minimum: 1 } ; "no pan step")]
+    #[test_case(
+        "--width 50 --chunk-width 60",
+        CliError::ChunkLargerThanWorld { flag: "--chunk-width".to_string() }
+        ; "chunk wider than world"
+    )]
+    #[test_case(
+        "--boundary mobius",
+        CliError::InvalidChoice {
+            flag: "--boundary".to_string(),
+            value: "mobius".to_string(),
+            choices: "dead, torus, klein, cross",
+        }
+        ; "unknown boundary"
+    )]
+    fn test_invalid_arguments(arguments: &str, expected: CliError) {
+        assert_eq!(parse(arguments), Err(expected));
+    }
+
+    #[test]
+    fn test_too_large_names_the_maximum() {
+        assert_eq!(parse("--width 4097").unwrap_err().to_string(), "--width must be at most 4096");
+    }
+
+    #[test]
+    fn test_invalid_rule_reports_the_rule_error() {
+        let error = parse("--rule B9/S23").unwrap_err();
+        assert_eq!(error.to_string(), "--rule: '9' is not a neighbor count between 0 and 8");
+    }
+}
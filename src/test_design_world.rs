@@ -0,0 +1,118 @@
+#[cfg(test)]
+pub mod test_design_editor {
+    use test_case::test_case;
+    use crate::design_world::{DesignWorld, FillSettings};
+    use crate::random::SeededRandom;
+
+    fn design_with_marks(marks: &[(u16, u16)]) -> DesignWorld {
+        let mut design_world = DesignWorld::new(20, 20);
+        for mark in marks {
+            design_world.marked_positions.insert(*mark, true);
+        }
+        return design_world;
+    }
+
+    fn sorted_marks(design_world: &DesignWorld) -> Vec<(i64, i64)> {
+        let mut marks = design_world.marked_cells();
+        marks.sort();
+        return marks;
+    }
+
+    #[test_case((2, 3), (6, 5) ; "anchor top left")]
+    #[test_case((6, 5), (2, 3) ; "anchor bottom right")]
+    #[test_case((6, 3), (2, 5) ; "anchor top right")]
+    fn test_selection_is_the_rectangle_between_anchor_and_cursor(anchor: (u16, u16), cursor: (u16, u16)) {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.selection_anchor = Some(anchor);
+        design_world.current_position = cursor;
+
+        assert_eq!(design_world.selection(), Some(((2, 3), (6, 5))));
+        assert!(design_world.is_selected((4, 4)));
+        assert!(!design_world.is_selected((7, 4)));
+    }
+
+    #[test]
+    fn test_copy_and_paste_elsewhere() {
+        let mut design_world = design_with_marks(&[(3, 3), (4, 4), (10, 10)]);
+        design_world.selection_anchor = Some((2, 2));
+        design_world.current_position = (5, 5);
+        design_world.copy_selection();
+        assert_eq!(design_world.selection_anchor, None);
+
+        design_world.current_position = (12, 2);
+        design_world.preview = design_world.clipboard.clone();
+        design_world.stamp_preview();
+        assert_eq!(sorted_marks(&design_world), vec![(3, 3), (4, 4), (10, 10), (13, 3), (14, 4)]);
+    }
+
+    #[test]
+    fn test_cut_clears_the_selection() {
+        let mut design_world = design_with_marks(&[(3, 3), (4, 4), (10, 10)]);
+        design_world.selection_anchor = Some((3, 3));
+        design_world.current_position = (4, 4);
+        design_world.cut_selection();
+        assert_eq!(sorted_marks(&design_world), vec![(10, 10)]);
+
+        design_world.current_position = (1, 1);
+        design_world.preview = design_world.clipboard.clone();
+        design_world.stamp_preview();
+        assert_eq!(sorted_marks(&design_world), vec![(1, 1), (2, 2), (10, 10)]);
+    }
+
+    #[test]
+    fn test_paste_drops_cells_past_the_border() {
+        let mut design_world = design_with_marks(&[(3, 3), (4, 3)]);
+        design_world.selection_anchor = Some((3, 3));
+        design_world.current_position = (4, 3);
+        design_world.copy_selection();
+
+        design_world.current_position = (18, 5);
+        design_world.preview = design_world.clipboard.clone();
+        design_world.stamp_preview();
+        assert_eq!(sorted_marks(&design_world), vec![(3, 3), (4, 3), (18, 5)]);
+    }
+
+    #[test]
+    fn test_clear_and_invert_the_whole_map() {
+        let mut design_world = design_with_marks(&[(3, 3), (10, 10)]);
+        design_world.clear_region();
+        assert!(sorted_marks(&design_world).is_empty());
+
+        design_world.invert_region();
+        assert_eq!(design_world.marked_cells().len(), 18 * 18);
+        assert!(!design_world.is_marked((0, 0)));
+    }
+
+    #[test]
+    fn test_region_operations_stay_inside_the_selection() {
+        let mut design_world = design_with_marks(&[(3, 3), (10, 10)]);
+        design_world.selection_anchor = Some((2, 2));
+        design_world.current_position = (4, 4);
+        design_world.invert_region();
+        assert_eq!(design_world.marked_cells().len(), 8 + 1);
+
+        design_world.clear_region();
+        assert_eq!(sorted_marks(&design_world), vec![(10, 10)]);
+    }
+
+    #[test]
+    fn test_fill_is_reproducible_from_its_seed() {
+        let mut first = DesignWorld::new(20, 20);
+        let mut second = DesignWorld::new(20, 20);
+        first.fill_region(&mut SeededRandom::new(42), 0.5);
+        second.fill_region(&mut SeededRandom::new(42), 0.5);
+
+        assert_eq!(sorted_marks(&first), sorted_marks(&second));
+        assert!(!sorted_marks(&first).is_empty());
+    }
+
+    #[test_case("", Ok(FillSettings { density: 0.35, seed: 9 }) ; "defaults")]
+    #[test_case("0.5", Ok(FillSettings { density: 0.5, seed: 9 }) ; "density only")]
+    #[test_case(" 0.1  77 ", Ok(FillSettings { density: 0.1, seed: 77 }) ; "density and seed")]
+    #[test_case("1.5", Err("Density must be between 0 and 1, got 1.5".to_string()) ; "density too high")]
+    #[test_case("0.5 abc", Err("Seed must be a whole number, got abc".to_string()) ; "bad seed")]
+    #[test_case("0.5 1 2", Err("Unexpected 2, expected density [seed]".to_string()) ; "extra field")]
+    fn test_parse_fill_settings(input: &str, expected: Result<FillSettings, String>) {
+        assert_eq!(FillSettings::parse(input, 0.35, 9), expected);
+    }
+}
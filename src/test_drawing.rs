@@ -0,0 +1,127 @@
+#[cfg(test)]
+pub mod test_drawing_tools {
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::design_world_display::DesignWorldDisplay;
+    use crate::drawing::{ellipse, line, rectangle, DrawTool};
+    use crate::world_display::MoveDirections;
+
+    fn sorted_marks(design_world: &DesignWorld) -> Vec<(i64, i64)> {
+        let mut marks = design_world.marked_cells();
+        marks.sort();
+        return marks;
+    }
+
+    #[test_case((1, 1), (4, 1), vec![(1, 1), (2, 1), (3, 1), (4, 1)] ; "horizontal")]
+    #[test_case((2, 4), (2, 2), vec![(2, 4), (2, 3), (2, 2)] ; "vertical upwards")]
+    #[test_case((0, 0), (3, 3), vec![(0, 0), (1, 1), (2, 2), (3, 3)] ; "diagonal")]
+    #[test_case((0, 0), (4, 2), vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)] ; "shallow")]
+    #[test_case((5, 5), (5, 5), vec![(5, 5)] ; "single cell")]
+    fn test_line(start: (u16, u16), end: (u16, u16), expected: Vec<(u16, u16)>) {
+        assert_eq!(line(start, end), expected);
+    }
+
+    #[test_case(false, 8 ; "hollow")]
+    #[test_case(true, 9 ; "filled")]
+    fn test_rectangle(filled: bool, expected_cells: usize) {
+        let cells = rectangle((3, 3), (1, 1), filled);
+        assert_eq!(cells.len(), expected_cells);
+        assert_eq!(cells.contains(&(2, 2)), filled);
+    }
+
+    #[test]
+    fn test_ellipse_outline_is_symmetric_and_touches_every_side() {
+        let cells = ellipse((2, 1), (12, 7), false);
+        for (x, y) in &cells {
+            assert!(cells.contains(&(14 - x, *y)));
+            assert!(cells.contains(&(*x, 8 - y)));
+        }
+        assert!(cells.contains(&(7, 1)));
+        assert!(cells.contains(&(7, 7)));
+        assert!(cells.contains(&(2, 4)));
+        assert!(cells.contains(&(12, 4)));
+        assert!(!cells.contains(&(7, 4)));
+        assert!(!cells.contains(&(2, 1)));
+    }
+
+    #[test]
+    fn test_filled_ellipse_covers_the_outline() {
+        let outline = ellipse((1, 1), (8, 5), false);
+        let filled = ellipse((1, 1), (8, 5), true);
+        assert!(outline.iter().all(|cell| filled.contains(cell)));
+        assert!(filled.contains(&(4, 3)));
+    }
+
+    #[test]
+    fn test_shape_tool_draws_from_the_anchor_to_the_cursor() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.tool = DrawTool::Line;
+        design_world.current_position = (2, 2);
+        design_world.use_tool();
+        design_world.current_position = (5, 2);
+        assert_eq!(design_world.tool_shape().len(), 4);
+
+        design_world.use_tool();
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2), (3, 2), (4, 2), (5, 2)]);
+        assert_eq!(design_world.tool_anchor, None);
+        assert_eq!(design_world.history.undo_len(), 1);
+    }
+
+    #[test]
+    fn test_pen_stroke_is_one_undo_step() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.tool = DrawTool::Pen;
+        design_world.current_position = (2, 2);
+        design_world.use_tool();
+        design_world.move_drawing(MoveDirections::Right);
+        design_world.move_drawing(MoveDirections::Up);
+        design_world.use_tool();
+        design_world.move_drawing(MoveDirections::Right);
+
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2), (3, 2), (3, 3)]);
+        design_world.undo();
+        assert!(sorted_marks(&design_world).is_empty());
+    }
+
+    #[test]
+    fn test_flood_fill_stops_at_marks_and_the_border() {
+        let mut design_world = DesignWorld::new(10, 10);
+        design_world.tool = DrawTool::Rectangle;
+        design_world.current_position = (2, 2);
+        design_world.use_tool();
+        design_world.current_position = (5, 5);
+        design_world.use_tool();
+
+        design_world.tool = DrawTool::FloodFill;
+        design_world.current_position = (3, 3);
+        design_world.use_tool();
+        assert_eq!(design_world.marked_cells().len(), 16);
+
+        design_world.current_position = (1, 1);
+        design_world.use_tool();
+        assert_eq!(design_world.marked_cells().len(), 8 * 8);
+    }
+
+    #[test_case(4, DesignWorldDisplay::HEADER_LINES + 3, Some((4, 3)) ; "on the grid")]
+    #[test_case(4, DesignWorldDisplay::HEADER_LINES - 1, None ; "in the header")]
+    #[test_case(20, DesignWorldDisplay::HEADER_LINES + 3, None ; "right of the grid")]
+    #[test_case(4, DesignWorldDisplay::HEADER_LINES + 20, None ; "below the grid")]
+    fn test_mouse_position_skips_the_header(column: u16, row: u16, expected: Option<(u16, u16)>) {
+        let design_world = DesignWorld::new(20, 20);
+        assert_eq!(DesignWorldDisplay::position_at(&design_world, column, row), expected);
+    }
+
+    #[test]
+    fn test_click_and_drag_paint_one_undo_step() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.click((2, 2));
+        design_world.drag_to((5, 2));
+        design_world.drag_to((0, 2));
+        design_world.end_drag();
+
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2), (3, 2), (4, 2), (5, 2)]);
+        assert_eq!(design_world.current_position, (5, 2));
+        design_world.undo();
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2)]);
+    }
+}
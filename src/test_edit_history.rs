@@ -0,0 +1,123 @@
+#[cfg(test)]
+pub mod test_undo_redo {
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::edit_history::{CellChange, EditHistory};
+    use crate::pattern_file::Pattern;
+    use crate::random::SeededRandom;
+
+    fn sorted_marks(design_world: &DesignWorld) -> Vec<(i64, i64)> {
+        let mut marks = design_world.marked_cells();
+        marks.sort();
+        return marks;
+    }
+
+    fn change(x: u16) -> CellChange {
+        return CellChange { position: (x, 1), before: false, after: true };
+    }
+
+    #[test]
+    fn test_toggle_undo_and_redo() {
+        let mut design_world = DesignWorld::new(10, 10);
+        design_world.current_position = (3, 4);
+        design_world.toggle_mark();
+        design_world.current_position = (5, 5);
+        design_world.toggle_mark();
+
+        assert!(design_world.undo());
+        assert_eq!(sorted_marks(&design_world), vec![(3, 4)]);
+        assert!(design_world.undo());
+        assert!(sorted_marks(&design_world).is_empty());
+        assert!(!design_world.undo());
+
+        assert!(design_world.redo());
+        assert!(design_world.redo());
+        assert_eq!(sorted_marks(&design_world), vec![(3, 4), (5, 5)]);
+        assert!(!design_world.redo());
+    }
+
+    #[test]
+    fn test_stamp_is_one_step_and_keeps_earlier_marks() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.current_position = (2, 2);
+        design_world.toggle_mark();
+        let pattern: Pattern = [((0, 0), true), ((1, 0), true), ((0, 1), true)].into_iter().collect();
+        design_world.stamp(&pattern, (2, 2));
+
+        assert_eq!(design_world.history.undo_len(), 2);
+        design_world.undo();
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_cut_and_fill_undo() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.fill_region(&mut SeededRandom::new(7), 0.5);
+        let randomized = sorted_marks(&design_world);
+        design_world.selection_anchor = Some((1, 1));
+        design_world.current_position = (10, 10);
+        design_world.cut_selection();
+
+        design_world.undo();
+        assert_eq!(sorted_marks(&design_world), randomized);
+        design_world.undo();
+        assert!(sorted_marks(&design_world).is_empty());
+    }
+
+    #[test]
+    fn test_history_survives_resizing() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.current_position = (3, 3);
+        design_world.toggle_mark();
+        design_world.current_position = (15, 15);
+        design_world.toggle_mark();
+
+        design_world.resize(10, 10);
+        assert!(design_world.undo());
+        assert!(design_world.clipped_marks.is_empty());
+        assert!(design_world.undo());
+        assert!(sorted_marks(&design_world).is_empty());
+        assert!(design_world.redo());
+        assert!(design_world.redo());
+        assert_eq!(design_world.clipped_marks, vec![(15, 15)]);
+
+        design_world.resize(20, 20);
+        assert_eq!(sorted_marks(&design_world), vec![(3, 3), (15, 15)]);
+        assert!(design_world.undo());
+        assert_eq!(sorted_marks(&design_world), vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_new_edit_drops_the_redo_stack() {
+        let mut design_world = DesignWorld::new(10, 10);
+        design_world.toggle_mark();
+        design_world.undo();
+        design_world.current_position = (2, 2);
+        design_world.toggle_mark();
+
+        assert!(!design_world.redo());
+        assert_eq!(sorted_marks(&design_world), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_edits_that_change_nothing_are_not_recorded() {
+        let mut design_world = DesignWorld::new(10, 10);
+        design_world.stamp(&Pattern::new(), (1, 1));
+
+        assert_eq!(design_world.history.undo_len(), 0);
+    }
+
+    #[test_case(3, 100, 3 ; "edit count bound")]
+    #[test_case(100, 4, 2 ; "changed cell bound")]
+    fn test_history_forgets_the_oldest_edits(max_edits: usize, max_changes: usize, expected: usize) {
+        let mut history = EditHistory::new(max_edits, max_changes);
+        for x in 0..5 {
+            history.record(change(x));
+            history.record(change(x + 10));
+            history.commit();
+        }
+
+        assert_eq!(history.undo_len(), expected);
+        assert_eq!(history.undo().unwrap()[0], change(4));
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+pub mod test_diff_renderer {
+    use test_case::test_case;
+    use crossterm::cursor::MoveTo;
+    use crossterm::queue;
+    use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+    use crate::frame_buffer::{DiffRenderer, FrameBuffer, FrameCell};
+
+    const ALIVE: FrameCell = FrameCell { glyph: 'X', color: Some(Color::Yellow) };
+
+    fn rendered(renderer: &mut DiffRenderer, frame: &FrameBuffer) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::new();
+        renderer.render(&mut out, frame.clone()).unwrap();
+        return out;
+    }
+
+    #[test]
+    fn test_unchanged_frame_writes_nothing() {
+        let mut renderer = DiffRenderer::new();
+        let mut frame = FrameBuffer::new(10, 4);
+        frame.put_text(0, "header");
+        frame.set(2, 2, ALIVE);
+
+        assert!(!rendered(&mut renderer, &frame).is_empty());
+        assert!(rendered(&mut renderer, &frame).is_empty());
+    }
+
+    #[test]
+    fn test_changed_run_shares_one_move_and_colour() {
+        let mut renderer = DiffRenderer::new();
+        let mut frame = FrameBuffer::new(10, 4);
+        rendered(&mut renderer, &frame);
+        frame.set(3, 1, ALIVE);
+        frame.set(4, 1, ALIVE);
+
+        let mut expected: Vec<u8> = Vec::new();
+        queue!(expected, MoveTo(3, 1), SetForegroundColor(Color::Yellow), Print('X'), Print('X'), ResetColor).unwrap();
+        assert_eq!(rendered(&mut renderer, &frame), expected);
+    }
+
+    #[test_case(5, 1 ; "short gap is reprinted")]
+    #[test_case(8, 2 ; "long gap is skipped")]
+    fn test_gaps_between_changes(second_x: usize, expected_moves: usize) {
+        let mut renderer = DiffRenderer::new();
+        let mut frame = FrameBuffer::new(12, 2);
+        rendered(&mut renderer, &frame);
+        frame.put_text(0, "a");
+        frame.set(second_x, 0, FrameCell { glyph: 'b', color: None });
+
+        let output = String::from_utf8(rendered(&mut renderer, &frame)).unwrap();
+        assert_eq!(output.matches('H').count(), expected_moves);
+    }
+
+    #[test]
+    fn test_resize_and_invalidate_redraw_everything() {
+        let mut renderer = DiffRenderer::new();
+        let frame = FrameBuffer::new(10, 4);
+        rendered(&mut renderer, &frame);
+        renderer.invalidate();
+        assert!(!rendered(&mut renderer, &frame).is_empty());
+        assert!(!rendered(&mut renderer, &FrameBuffer::new(11, 4)).is_empty());
+    }
+
+    #[test]
+    fn test_text_is_cut_off_at_the_width() {
+        let mut frame = FrameBuffer::new(3, 1);
+        frame.put_text(0, "abcdef");
+        assert_eq!(frame.get(2, 0).glyph, 'c');
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+pub mod test_hash_life {
+    use test_case::test_case;
+    use crate::hashlife::{HashLife, MAX_STEP_LOG};
+    use crate::rule::Rule;
+    use crate::sparse_world::SparseWorld;
+    use crate::world::{StorageKind, World};
+
+    const R_PENTOMINO: [(i64, i64); 5] = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)];
+
+    fn sorted(mut cells: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+        cells.sort();
+        return cells;
+    }
+
+    #[test_case("B3/S23", 1 ; "conway one generation")]
+    #[test_case("B3/S23", 600 ; "conway six hundred generations")]
+    #[test_case("B36/S23", 300 ; "highlife")]
+    #[test_case("B2/S", 20 ; "seeds")]
+    fn test_matches_sparse_world(rulestring: &str, generations: u128) {
+        let rule = Rule::parse(rulestring).unwrap();
+        let mut hashlife = HashLife::from_cells(R_PENTOMINO, rule);
+        let mut sparse_world = SparseWorld::new();
+        for key in R_PENTOMINO {
+            sparse_world.insert_position(key);
+        }
+
+        hashlife.advance(generations);
+        for _ in 0..generations {
+            sparse_world.handle_generation(&rule);
+        }
+
+        assert_eq!(hashlife.generation, generations);
+        assert_eq!(
+            sorted(hashlife.live_cells()),
+            sorted(sparse_world.alive_tile_keys.iter().copied().collect())
+        );
+    }
+
+    #[test]
+    fn test_r_pentomino_stabilises() {
+        let mut hashlife = HashLife::from_cells(R_PENTOMINO, Rule::conway());
+        hashlife.advance_pow2(20);
+
+        // The R-pentomino settles down to 116 cells once its six gliders have escaped.
+        assert_eq!(hashlife.generation, 1 << 20);
+        assert_eq!(hashlife.live_cells().len(), 116);
+    }
+
+    #[test]
+    fn test_round_trip_through_world() {
+        let mut world = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in R_PENTOMINO {
+            world.insert_live_cell(key);
+        }
+
+        world.advance_generations(100);
+        let mut stepped = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in R_PENTOMINO {
+            stepped.insert_live_cell(key);
+        }
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for _ in 0..100 {
+            stepped.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+
+        assert_eq!(world.current_generation, 101);
+        assert_eq!(sorted(world.live_cells()), sorted(stepped.live_cells()));
+    }
+
+    #[test]
+    fn test_world_keeps_its_hashlife_between_jumps() {
+        let mut world = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in R_PENTOMINO {
+            world.insert_live_cell(key);
+        }
+        world.jump_exponent = 6;
+        world.jump();
+        assert!(world.hashlife.as_ref().is_some_and(|hashlife| hashlife.is_in_step_with(&world)));
+        world.jump();
+
+        let mut stepped = HashLife::from_cells(R_PENTOMINO, Rule::conway());
+        stepped.advance(128);
+        assert_eq!(world.current_generation, 129);
+        assert_eq!(sorted(world.live_cells()), sorted(stepped.live_cells()));
+        assert!(world.hashlife.is_some());
+    }
+
+    #[test]
+    fn test_world_rebuilds_its_hashlife_after_other_changes() {
+        let mut world = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in R_PENTOMINO {
+            world.insert_live_cell(key);
+        }
+        world.advance_generations(10);
+        world.insert_live_cell((-20, -20));
+        assert!(world.hashlife.is_none());
+
+        world.advance_generations(10);
+        world.rule = Rule::parse("B36/S23").unwrap();
+        assert!(!world.hashlife.as_ref().unwrap().is_in_step_with(&world));
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        world.rule = Rule::conway();
+        world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        assert!(!world.hashlife.as_ref().unwrap().is_in_step_with(&world));
+
+        // The stale HashLife is dropped rather than jumping from the wrong cells.
+        let mut expected = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        expected.replace_live_cells(world.live_cells());
+        expected.current_generation = world.current_generation;
+        world.advance_generations(5);
+        expected.advance_generations(5);
+        assert_eq!(sorted(world.live_cells()), sorted(expected.live_cells()));
+    }
+
+    #[test_case(10, 11, 9 ; "default")]
+    #[test_case(0, 1, 0 ; "single generation")]
+    #[test_case(60, 60, 59 ; "largest")]
+    fn test_jump_size(exponent: u32, larger: u32, smaller: u32) {
+        let mut world = World::new(10, 10, 10, 10, 0, 0, Rule::conway(), StorageKind::Tiles);
+        world.jump_exponent = exponent;
+        world.larger_jump();
+        assert_eq!(world.jump_exponent, larger);
+        world.jump_exponent = exponent;
+        world.smaller_jump();
+        assert_eq!(world.jump_exponent, smaller);
+    }
+
+    #[test]
+    fn test_largest_jump_runs() {
+        let mut world = World::new(40, 40, 40, 40, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in R_PENTOMINO {
+            world.insert_live_cell(key);
+        }
+        for _ in 0..100 {
+            world.larger_jump();
+        }
+        assert_eq!(world.jump_exponent, MAX_STEP_LOG);
+
+        world.jump();
+        world.jump();
+        assert_eq!(world.current_generation, 1 + (2_u128 << MAX_STEP_LOG));
+        assert_eq!(world.live_cells().len(), 116);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+pub mod test_macrocell_format {
+    use test_case::test_case;
+    use crate::macrocell::{read_macrocell, write_macrocell};
+    use crate::pattern_file::{PatternError, PatternInfo};
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+
+    fn sorted(mut cells: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+        cells.sort();
+        return cells;
+    }
+
+    #[test]
+    fn test_read_golly_file() {
+        let contents = "[M2] (golly 4.2)\n#R B3/S23\n#G 12\n$$$$$$*$.*$\n..*$*.*$\n4 0 0 1 2\n";
+        let (cells, info) = read_macrocell(contents).unwrap();
+
+        // The level 4 root covers -8..8 on both axes, the leaves are its bottom two quadrants.
+        assert_eq!(sorted(cells), vec![(-8, 6), (-7, 7), (0, 1), (2, 0), (2, 1)]);
+        assert_eq!(info.rule, Some(Rule::conway()));
+        assert_eq!(info.generation, Some(12));
+    }
+
+    #[test]
+    fn test_read_non_ascii_comments() {
+        let (cells, info) = read_macrocell("[M2]\n#ü x\n#N Gleitflügler\n*$\n").unwrap();
+
+        assert_eq!(cells.len(), 1);
+        assert_eq!(info.name.as_deref(), Some("Gleitflügler"));
+    }
+
+    #[test_case(vec![(0, 0), (1, 0), (2, 0)] ; "blinker at the origin")]
+    #[test_case(vec![(-5, 3), (-4, 4), (-6, 5), (-5, 5), (-4, 5)] ; "glider in negative space")]
+    #[test_case(vec![(0, 0), (i64::MAX, i64::MIN), (-1_000_000_007, 3)] ; "extremely sparse")]
+    fn test_cells_round_trip_exactly(cells: Vec<(i64, i64)>) {
+        let info = PatternInfo { rule: Some(Rule::conway()), generation: Some(7), ..PatternInfo::default() };
+        let contents = write_macrocell(&cells, &info);
+        let (read_back, read_info) = read_macrocell(&contents).unwrap();
+
+        assert_eq!(sorted(read_back), sorted(cells));
+        assert_eq!(read_info.generation, Some(7));
+    }
+
+    #[test]
+    fn test_repeated_blocks_are_shared() {
+        // 64 blinkers spread over a 1024x1024 area share a single leaf.
+        let mut cells = Vec::new();
+        for block_y in 0..8 {
+            for block_x in 0..8 {
+                for x in 0..3 {
+                    cells.push((block_x * 128 + x, block_y * 128));
+                }
+            }
+        }
+        let contents = write_macrocell(&cells, &PatternInfo::default());
+
+        let leaf_lines = contents.lines().filter(|line| line.starts_with(['.', '*', '$'])).count();
+        assert_eq!(leaf_lines, 1);
+        assert!(contents.lines().count() < 40);
+        assert_eq!(sorted(read_macrocell(&contents).unwrap().0), sorted(cells));
+    }
+
+    #[test_case("[M2]\n4 1 0 0 0\n", 2 ; "undefined child")]
+    #[test_case("[M2]\n**$\n5 1 0 0 0\n", 3 ; "child at the wrong level")]
+    #[test_case("[M2]\n*********$\n", 2 ; "leaf too wide")]
+    #[test_case("[M2]\n*$$$$$$$$$\n", 2 ; "leaf too tall")]
+    #[test_case(&format!("[M2]\n{}*\n", "$".repeat(300)), 2 ; "more rows than a u8 counts")]
+    #[test_case(&format!("[M2]\n{}\n", ".".repeat(300)), 2 ; "more columns than a u8 counts")]
+    #[test_case("[M2]\n1 0 0 0 1\n", 2 ; "multi-state node")]
+    fn test_read_errors_name_the_line(contents: &str, expected_line: usize) {
+        match read_macrocell(contents) {
+            Err(PatternError::Parse { line, .. }) => assert_eq!(line, expected_line),
+            other => panic!("expected a parse error, got {:?}", other.map(|(cells, _)| cells.len())),
+        }
+    }
+
+    #[test]
+    fn test_bounded_world_moves_to_the_plane_for_cells_that_do_not_fit() {
+        let mut world = World::new(10, 10, 10, 10, 0, 0, Rule::conway(), StorageKind::Tiles);
+        world.replace_live_cells(vec![(1, 1), (2, 2)]);
+        assert_eq!(world.storage_kind, StorageKind::Tiles);
+
+        world.replace_live_cells(vec![(-1, 1), (20, 2)]);
+        assert_eq!(world.storage_kind, StorageKind::Unbounded);
+        assert_eq!(sorted(world.live_cells()), vec![(-1, 1), (20, 2)]);
+    }
+}
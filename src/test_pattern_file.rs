@@ -0,0 +1,124 @@
+#[cfg(test)]
+pub mod test_pattern_files {
+    use std::path::Path;
+    use test_case::test_case;
+    use crate::life_format::{read_life_105, read_life_106, write_life_105, write_life_106};
+    use crate::pattern_file::{live_cells, PatternError, PatternFormat, PatternInfo};
+    use crate::plaintext::{read_plaintext, write_plaintext};
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+
+    const GLIDER_CELLS: [(u16, u16); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    const GLIDER_PLAINTEXT: &str = "\
+!Name: Glider
+!The smallest spaceship.
+.O
+..O
+OOO
+";
+
+    const GLIDER_LIFE_105: &str = "\
+#Life 1.05
+#D Glider
+#N
+#P -1 -1
+.*
+..*
+***
+";
+
+    #[test]
+    fn test_life_106_is_moved_to_the_origin() {
+        let (pattern, _) = read_life_106("#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+        assert_eq!(live_cells(&pattern), GLIDER_CELLS);
+    }
+
+    #[test_case("1\n", 1 ; "missing y")]
+    #[test_case("#C comment\n1 2 3\n", 2 ; "extra value")]
+    #[test_case("0 0\na 1\n", 2 ; "not a number")]
+    fn test_life_106_errors_name_the_line(contents: &str, expected_line: usize) {
+        match read_life_106(contents) {
+            Err(PatternError::Parse { line, .. }) => assert_eq!(line, expected_line),
+            other => panic!("expected a parse error, got {:?}", other.map(|(pattern, _)| pattern.len())),
+        }
+    }
+
+    #[test_case("0 0\n70000 0\n" ; "wider than a pattern")]
+    #[test_case("-9223372036854775808 0\n9223372036854775807 0\n" ; "spread over every i64")]
+    #[test_case("0 -9223372036854775808\n0 9223372036854775807\n" ; "tall over every i64")]
+    fn test_life_106_too_large(contents: &str) {
+        assert!(matches!(read_life_106(contents), Err(PatternError::TooLarge)));
+    }
+
+    #[test]
+    fn test_life_106_round_trips() {
+        let (pattern, info) = read_life_106("#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n").unwrap();
+        assert_eq!(write_life_106(&pattern, &info), "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n");
+    }
+
+    #[test]
+    fn test_life_105_blocks_are_placed_at_their_offsets() {
+        let contents = "#Life 1.05\n#R 23/36\n#P 0 0\n*\n#P 4 2\n**\n";
+        let (pattern, info) = read_life_105(contents).unwrap();
+
+        assert_eq!(live_cells(&pattern), vec![(0, 0), (4, 2), (5, 2)]);
+        assert_eq!(info.rule, Some(Rule::from_counts(&[3, 6], &[2, 3])));
+    }
+
+    #[test]
+    fn test_life_105_round_trips() {
+        let (pattern, info) = read_life_105(GLIDER_LIFE_105).unwrap();
+        assert_eq!(live_cells(&pattern), GLIDER_CELLS);
+        assert_eq!(info.rule, Some(Rule::conway()));
+        assert_eq!(write_life_105(&pattern, &info), GLIDER_LIFE_105.replace("-1 -1", "0 0"));
+    }
+
+    #[test]
+    fn test_plaintext_round_trips() {
+        let (pattern, info) = read_plaintext(GLIDER_PLAINTEXT).unwrap();
+        assert_eq!(live_cells(&pattern), GLIDER_CELLS);
+        assert_eq!(info.name.as_deref(), Some("Glider"));
+        assert_eq!(write_plaintext(&pattern, &info), GLIDER_PLAINTEXT);
+    }
+
+    #[test]
+    fn test_plaintext_keeps_empty_rows() {
+        let (pattern, _) = read_plaintext("O\n\n.\nO\n").unwrap();
+        assert_eq!(live_cells(&pattern), vec![(0, 0), (0, 3)]);
+        assert_eq!(write_plaintext(&pattern, &PatternInfo::default()), "O\n.\n.\nO\n");
+    }
+
+    #[test_case("glider.txt", GLIDER_LIFE_105, PatternFormat::Life105 ; "life 1.05 header")]
+    #[test_case("glider.rle", "#Life 1.06\n0 0\n", PatternFormat::Life106 ; "life 1.06 header beats extension")]
+    #[test_case("glider", GLIDER_PLAINTEXT, PatternFormat::Plaintext ; "plaintext comment")]
+    #[test_case("glider.cells", "#C made by hand\nx = 3, y = 3\nbo$2bo$3o!", PatternFormat::Rle ; "rle header beats extension")]
+    #[test_case("glider.cells", ".O\n..O\nOOO\n", PatternFormat::Plaintext ; "plaintext extension")]
+    #[test_case("glider.lif", "1 0\n2 1\n", PatternFormat::Life106 ; "life extension")]
+    #[test_case("glider", "1 0\n2 1\n", PatternFormat::Life106 ; "bare coordinates")]
+    #[test_case("glider", ".O\n..O\nOOO\n", PatternFormat::Plaintext ; "bare rows")]
+    #[test_case("glider", "bo$2bo$3o!", PatternFormat::Rle ; "bare runs")]
+    fn test_detect(file_name: &str, contents: &str, expected: PatternFormat) {
+        assert_eq!(PatternFormat::detect(Path::new(file_name), contents), expected);
+    }
+
+    #[test_case(PatternFormat::Rle ; "rle")]
+    #[test_case(PatternFormat::Plaintext ; "plaintext")]
+    #[test_case(PatternFormat::Life105 ; "life 1.05")]
+    #[test_case(PatternFormat::Life106 ; "life 1.06")]
+    fn test_every_format_feeds_the_world(format: PatternFormat) {
+        let (glider, info) = read_plaintext(GLIDER_PLAINTEXT).unwrap();
+        let contents = format.write(&glider, &info);
+        let detected = PatternFormat::detect(Path::new("glider"), &contents);
+        let (pattern, _) = detected.read(&contents).unwrap();
+
+        let mut world = World::new(8, 8, 8, 8, 0, 0, Rule::conway(), StorageKind::Tiles);
+        world.reset_game_world(pattern.iter());
+        let mut world_cells = world.live_cells();
+        world_cells.sort_by_key(|position| (position.1, position.0));
+        let expected: Vec<(i64, i64)> =
+            GLIDER_CELLS.iter().map(|(x, y)| (*x as i64, *y as i64)).collect();
+        assert_eq!(detected, format);
+        assert_eq!(world_cells, expected);
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+pub mod test_library {
+    use std::collections::HashSet;
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::pattern_library::PATTERN_LIBRARY;
+    use crate::rule::Rule;
+    use crate::sparse_world::SparseWorld;
+
+    fn library_world(name: &str) -> SparseWorld {
+        let library_pattern = PATTERN_LIBRARY.iter().find(|entry| entry.name == name).unwrap();
+        let mut world = SparseWorld::new();
+        for ((x, y), alive) in library_pattern.pattern() {
+            if alive {
+                world.insert_position((x as i64, y as i64));
+            }
+        }
+        return world;
+    }
+
+    fn step(world: &mut SparseWorld, generations: usize) {
+        for _ in 0..generations {
+            world.handle_generation(&Rule::conway());
+        }
+    }
+
+    #[test]
+    fn test_every_entry_parses_under_a_unique_name() {
+        let mut names = HashSet::new();
+        for library_pattern in PATTERN_LIBRARY.iter() {
+            assert!(!library_pattern.pattern().is_empty(), "{} is empty", library_pattern.name);
+            assert!(names.insert(library_pattern.name), "{} is listed twice", library_pattern.name);
+        }
+    }
+
+    #[test_case("Block", 1 ; "block")]
+    #[test_case("Beehive", 1 ; "beehive")]
+    #[test_case("Blinker", 2 ; "blinker")]
+    #[test_case("Toad", 2 ; "toad")]
+    #[test_case("Beacon", 2 ; "beacon")]
+    #[test_case("Pulsar", 3 ; "pulsar")]
+    #[test_case("Pentadecathlon", 15 ; "pentadecathlon")]
+    fn test_oscillators_return_to_their_start(name: &str, period: usize) {
+        let mut world = library_world(name);
+        let start = world.alive_tile_keys.clone();
+        step(&mut world, period);
+        assert_eq!(world.alive_tile_keys, start);
+    }
+
+    #[test_case("Glider", 4, (1, 1) ; "glider")]
+    #[test_case("LWSS", 4, (-2, 0) ; "lightweight")]
+    #[test_case("MWSS", 4, (-2, 0) ; "middleweight")]
+    #[test_case("HWSS", 4, (-2, 0) ; "heavyweight")]
+    fn test_spaceships_move(name: &str, period: usize, offset: (i64, i64)) {
+        let mut world = library_world(name);
+        let moved: HashSet<(i64, i64)> = world
+            .alive_tile_keys
+            .iter()
+            .map(|(x, y)| (x + offset.0, y + offset.1))
+            .collect();
+        step(&mut world, period);
+        assert_eq!(world.alive_tile_keys, moved);
+    }
+
+    #[test]
+    fn test_diehard_vanishes_after_130_generations() {
+        let mut world = library_world("Diehard");
+        step(&mut world, 129);
+        assert!(world.population() > 0);
+        step(&mut world, 1);
+        assert_eq!(world.population(), 0);
+    }
+
+    #[test]
+    fn test_gosper_gun_adds_a_glider_every_30_generations() {
+        let mut world = library_world("Gosper glider gun");
+        assert_eq!(world.population(), 36);
+        step(&mut world, 120);
+        // Four gliders of five cells have left the gun, which is back in its starting phase.
+        assert_eq!(world.population(), 36 + 4 * 5);
+    }
+
+    #[test]
+    fn test_stamp_and_preview_at_the_cursor() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.current_position = (5, 6);
+        let glider = PATTERN_LIBRARY[0].pattern();
+        design_world.preview = Some(glider.clone());
+        assert!(design_world.is_previewed((6, 6)));
+        assert!(!design_world.is_previewed((5, 6)));
+
+        design_world.stamp(&glider, design_world.current_position);
+        let mut marked = design_world.marked_cells();
+        marked.sort();
+        assert_eq!(marked, vec![(5, 8), (6, 6), (6, 8), (7, 7), (7, 8)]);
+    }
+}
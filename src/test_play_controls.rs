@@ -0,0 +1,92 @@
+#[cfg(test)]
+pub mod test_speed_controls {
+    use std::time::Duration;
+    use test_case::test_case;
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World, MAX_FRAME_INTERVAL_MS};
+
+    fn world(frame_interval_ms: usize) -> World {
+        return World::new(20, 20, 20, 20, frame_interval_ms, 0, Rule::conway(), StorageKind::Tiles);
+    }
+
+    #[test_case(36, 18 ; "halves")]
+    #[test_case(1, 1 ; "stops at one millisecond")]
+    fn test_faster(interval: usize, expected: usize) {
+        let mut world = world(interval);
+        world.faster();
+        assert_eq!(world.frame_interval_ms, expected);
+    }
+
+    #[test_case(36, 72 ; "doubles")]
+    #[test_case(1500, MAX_FRAME_INTERVAL_MS ; "stops at the maximum")]
+    #[test_case(0, 1 ; "leaves zero")]
+    fn test_slower(interval: usize, expected: usize) {
+        let mut world = world(interval);
+        world.slower();
+        assert_eq!(world.frame_interval_ms, expected);
+    }
+
+    #[test]
+    fn test_slower_leaves_uncapped_at_the_old_interval() {
+        let mut world = world(36);
+        world.uncapped = true;
+        world.faster();
+        world.slower();
+        assert!(!world.uncapped);
+        assert_eq!(world.frame_interval_ms, 36);
+    }
+
+    #[test_case(StorageKind::Tiles ; "tiles")]
+    #[test_case(StorageKind::BitPacked ; "bit packed")]
+    fn test_stepped_storage_queues_advances(storage_kind: StorageKind) {
+        let mut world = World::new(20, 20, 20, 20, 36, 0, Rule::conway(), storage_kind);
+        world.advance_generations(u128::MAX);
+        world.advance_generations(5);
+        assert_eq!(world.pending_generations, u128::MAX);
+        assert_eq!(world.current_generation, 1);
+
+        world.step_pending_generations(Duration::ZERO);
+        assert_eq!(world.current_generation, 2);
+        assert_eq!(world.pending_generations, u128::MAX - 1);
+    }
+
+    #[test]
+    fn test_queued_generations_run_out() {
+        let mut world = world(36);
+        for cell in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            world.insert_live_cell(cell);
+        }
+        world.advance_generations(4);
+        world.step_pending_generations(Duration::from_secs(60));
+        assert_eq!(world.pending_generations, 0);
+        assert_eq!(world.current_generation, 5);
+        let mut cells = world.live_cells();
+        cells.sort();
+        assert_eq!(cells, vec![(1, 3), (2, 1), (2, 3), (3, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn test_clearing_the_world_drops_queued_generations() {
+        let mut world = world(36);
+        world.advance_generations(1000);
+        world.replace_live_cells(vec![(3, 3)]);
+        assert_eq!(world.pending_generations, 0);
+    }
+
+    #[test]
+    fn test_unbounded_conway_jumps_straight_there() {
+        let mut world = World::new(20, 20, 20, 20, 36, 0, Rule::conway(), StorageKind::Unbounded);
+        world.advance_generations(1 << 40);
+        assert_eq!(world.pending_generations, 0);
+        assert_eq!(world.current_generation, (1 << 40) + 1);
+    }
+
+    #[test_case("2305843009213693951", Ok(2305843009213693951) ; "largest")]
+    #[test_case(" 12 ", Ok(12) ; "padded")]
+    #[test_case("2305843009213693952", Err("Can advance at most 2305843009213693951 generations at once, got 2305843009213693952".to_string()) ; "oversized")]
+    #[test_case("340282366920938463463374607431768211456", Err("Expected a number of generations above 0, got 340282366920938463463374607431768211456".to_string()) ; "past u128")]
+    #[test_case("0", Err("Expected a number of generations above 0, got 0".to_string()) ; "zero")]
+    fn test_parse_generations(input: &str, expected: Result<u128, String>) {
+        assert_eq!(World::parse_generations(input), expected);
+    }
+}
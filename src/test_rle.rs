@@ -0,0 +1,104 @@
+#[cfg(test)]
+pub mod test_run_length_encoding {
+    use test_case::test_case;
+    use crate::pattern_file::{normalize, PatternError, PatternInfo};
+    use crate::rle::{read_rle, write_rle};
+    use crate::rule::Rule;
+
+    const GLIDER: &str = "\
+#N Glider
+#O Richard K. Guy
+#C The smallest, most common, and first discovered spaceship.
+x = 3, y = 3, rule = B3/S23
+bo$2bo$3o!
+";
+
+    fn sorted_cells(pattern: &crate::pattern_file::Pattern) -> Vec<(u16, u16)> {
+        let mut cells: Vec<(u16, u16)> =
+            pattern.iter().filter(|(_, alive)| **alive).map(|(cell, _)| *cell).collect();
+        cells.sort();
+        return cells;
+    }
+
+    #[test]
+    fn test_read_glider_with_comments() {
+        let (pattern, info) = read_rle(GLIDER).unwrap();
+
+        assert_eq!(sorted_cells(&pattern), vec![(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)]);
+        assert_eq!(info.name.as_deref(), Some("Glider"));
+        assert_eq!(info.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(info.comments.len(), 1);
+        assert_eq!(info.rule, Some(Rule::conway()));
+    }
+
+    #[test]
+    fn test_read_non_ascii_comments() {
+        let (pattern, info) = read_rle("#é hello\n#N Gleitflügler\n#C ü\nx = 1, y = 1\no!").unwrap();
+
+        assert_eq!(sorted_cells(&pattern), vec![(0, 0)]);
+        assert_eq!(info.name.as_deref(), Some("Gleitflügler"));
+        assert_eq!(info.comments, vec!["ü".to_string()]);
+    }
+
+    #[test]
+    fn test_write_glider_round_trips() {
+        let (pattern, info) = read_rle(GLIDER).unwrap();
+        assert_eq!(write_rle(&pattern, &info), GLIDER);
+    }
+
+    #[test_case("x = 1, y = 3\no2$o!", vec![(0, 0), (0, 2)] ; "blank rows")]
+    #[test_case("x = 12, y = 1\n12o!", (0..12).map(|x| (x, 0)).collect() ; "two digit run")]
+    #[test_case("3o$\no\nbo!", vec![(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)] ; "runs split across lines")]
+    #[test_case("x = 3, y = 1, rule = B2/S/C3\nABA!", vec![(0, 0), (2, 0)] ; "generations letters")]
+    fn test_read_cells(contents: &str, expected: Vec<(u16, u16)>) {
+        let (pattern, _) = read_rle(contents).unwrap();
+        assert_eq!(sorted_cells(&pattern), expected);
+    }
+
+    #[test]
+    fn test_header_rule_ignores_bounded_grid_suffix() {
+        let (_, info) = read_rle("x = 1, y = 1, rule = B36/S23:T100,100\no!").unwrap();
+        assert_eq!(info.rule, Some(Rule::from_counts(&[3, 6], &[2, 3])));
+    }
+
+    #[test_case("x = 1, y = 1, rule = B9/S23\no!", 1 ; "bad rule")]
+    #[test_case("x = three, y = 1\no!", 1 ; "bad size")]
+    #[test_case("#C fine\nx = 2, y = 1\nz!", 3 ; "bad cell tag")]
+    fn test_read_errors_name_the_line(contents: &str, expected_line: usize) {
+        match read_rle(contents) {
+            Err(PatternError::Parse { line, .. }) => assert_eq!(line, expected_line),
+            other => panic!("expected a parse error, got {:?}", other.map(|(pattern, _)| pattern.len())),
+        }
+    }
+
+    #[test_case("99999999999999999999999o!" ; "huge live run")]
+    #[test_case("99999999999999999999999b!" ; "huge dead run")]
+    #[test_case("99999999999999999999999$o!" ; "huge row run")]
+    #[test_case("65536bo!" ; "cell past the widest pattern")]
+    #[test_case("40000b40000bo!" ; "runs adding up past the widest pattern")]
+    fn test_read_rejects_runs_past_the_widest_pattern(contents: &str) {
+        assert!(matches!(read_rle(contents), Err(PatternError::TooLarge)));
+    }
+
+    #[test]
+    fn test_read_keeps_a_cell_on_the_last_column() {
+        let (pattern, _) = read_rle("o65534bo!").unwrap();
+        assert_eq!(sorted_cells(&pattern), vec![(0, 0), (65535, 0)]);
+    }
+
+    #[test]
+    fn test_write_wraps_long_lines_and_skips_leading_rows() {
+        let cells: Vec<(i64, i64)> = (0..100).step_by(2).map(|x| (x, 3)).collect();
+        let mut pattern = normalize(cells).unwrap();
+        // Shift the row down, like a design map saved without trimming.
+        pattern = pattern.into_iter().map(|((x, y), alive)| ((x, y + 2), alive)).collect();
+
+        let contents = write_rle(&pattern, &PatternInfo::default());
+        assert!(contents.lines().all(|line| line.len() <= 70));
+        assert!(contents.lines().nth(1).unwrap().starts_with("2$obo"));
+
+        let (read_back, _) = read_rle(&contents).unwrap();
+        assert_eq!(read_back.len(), 50);
+        assert!(read_back.contains_key(&(98, 0)));
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+pub mod test_rules {
+    use test_case::test_case;
+    use crate::rule::{Rule, RuleParseError};
+    use crate::boundary::BoundaryMode;
+    use crate::random::SeededRandom;
+    use crate::tile::DEAD;
+    use crate::world::{StorageKind, World};
+
+    #[test_case("B3/S23", "B3/S23" ; "conway")]
+    #[test_case("b36/s23", "B36/S23" ; "highlife lowercase")]
+    #[test_case("S34678/B3678", "B3678/S34678" ; "day and night reversed sections")]
+    #[test_case("23/3", "B3/S23" ; "survival birth notation")]
+    #[test_case("B2/S", "B2/S" ; "seeds")]
+    #[test_case("/2", "B2/S" ; "seeds survival birth notation")]
+    #[test_case("/2/3", "B2/S/C3" ; "brians brain survival birth notation")]
+    #[test_case("345/2/4", "B2/S345/C4" ; "star wars survival birth notation")]
+    #[test_case("B2/S/C3", "B2/S/C3" ; "brians brain")]
+    #[test_case("B3/S23/2", "B3/S23" ; "two states is life like")]
+    fn test_parse_rule(rulestring: &str, expected: &str) {
+        let rule = Rule::parse(rulestring).unwrap();
+        assert_eq!(rule.to_string(), expected);
+    }
+
+    #[test_case("", RuleParseError::Empty ; "empty")]
+    #[test_case("B3S23", RuleParseError::MissingSeparator ; "missing separator")]
+    #[test_case("B3/S23/3/1", RuleParseError::TooManySections ; "too many sections")]
+    #[test_case("B39/S23", RuleParseError::InvalidNeighborCount('9') ; "count out of range")]
+    #[test_case("B3/Sx", RuleParseError::InvalidNeighborCount('x') ; "not a digit")]
+    #[test_case("B3/B23", RuleParseError::DuplicatePrefix('B') ; "duplicate birth")]
+    #[test_case("B03/S23", RuleParseError::BirthOnZero ; "birth on zero")]
+    #[test_case("B2/S/C1", RuleParseError::InvalidStateCount("C1".to_string()) ; "too few states")]
+    #[test_case("B2/S/C300", RuleParseError::InvalidStateCount("C300".to_string()) ; "too many states")]
+    fn test_parse_rule_errors(rulestring: &str, expected: RuleParseError) {
+        assert_eq!(Rule::parse(rulestring), Err(expected));
+    }
+
+    #[test]
+    fn test_seeds_kills_every_live_cell() {
+        let mut world = World::new(20, 20, 10, 10, 0, 0, Rule::parse("B2/S").unwrap(), StorageKind::Tiles);
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        World::insert_position(&mut world.tiles, &mut world.alive_tile_keys, (5, 5));
+        World::insert_position(&mut world.tiles, &mut world.alive_tile_keys, (6, 5));
+
+        world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+
+        assert!(!world.tiles[&(5, 5)].is_alive());
+        assert!(!world.tiles[&(6, 5)].is_alive());
+        assert!(world.tiles[&(5, 4)].is_alive());
+        assert!(world.tiles[&(6, 6)].is_alive());
+        assert_eq!(world.alive_tile_keys.len(), 4);
+    }
+
+    #[test_case(StorageKind::Tiles, 4 ; "parallel tiles")]
+    #[test_case(StorageKind::BitPacked, 1 ; "bit packed")]
+    #[test_case(StorageKind::Unbounded, 1 ; "unbounded")]
+    fn test_star_wars_matches_serial_tiles(storage_kind: StorageKind, worker_threads: usize) {
+        let rule = Rule::parse("345/2/4").unwrap();
+        let mut serial = World::new(64, 64, 64, 10, 0, 0, rule, StorageKind::Tiles);
+        let mut other = World::new(64, 64, 64, 10, 0, 0, rule, storage_kind);
+        serial.boundary_mode = BoundaryMode::Torus;
+        other.boundary_mode = BoundaryMode::Torus;
+        other.worker_threads = worker_threads;
+        let mut random = SeededRandom::new(3);
+        for y in 20..44 {
+            for x in 20..44 {
+                if random.chance(0.5) {
+                    serial.insert_live_cell((x, y));
+                    other.insert_live_cell((x, y));
+                }
+            }
+        }
+        if storage_kind == StorageKind::Tiles {
+            assert!(other.uses_parallel_generation());
+        }
+
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        let mut saw_dying_cell = false;
+        // Short enough that nothing reaches the torus edges the unbounded plane doesn't have.
+        for generation in 0..8 {
+            serial.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            other.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            for y in 0..64 {
+                for x in 0..64 {
+                    let state = serial.cell_state(x, y);
+                    saw_dying_cell |= state > 1;
+                    assert_eq!(state, other.cell_state(x, y), "({}, {}) at generation {}", x, y, generation);
+                }
+            }
+        }
+        assert!(saw_dying_cell);
+    }
+
+    #[test]
+    fn test_brians_brain_cells_die_after_one_dying_state() {
+        let rule = Rule::parse("/2/3").unwrap();
+        let mut world = World::new(10, 10, 10, 10, 0, 0, rule, StorageKind::Tiles);
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        world.insert_live_cell((4, 4));
+
+        world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        assert_eq!(world.cell_state(4, 4), 2);
+        assert!(world.alive_tile_keys.is_empty());
+
+        world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        assert_eq!(world.cell_state(4, 4), DEAD);
+        assert!(world.dying_tile_keys.is_empty());
+    }
+}
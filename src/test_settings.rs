@@ -0,0 +1,97 @@
+#[cfg(test)]
+pub mod test_settings_form {
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::rule::Rule;
+    use crate::settings::SettingsForm;
+    use crate::view_limits::ViewLimits;
+    use crate::world::{StorageKind, World};
+
+    fn form_with(values: [&str; 7]) -> SettingsForm {
+        let world = World::new(10, 10, 5, 5, 0, 0, Rule::conway(), StorageKind::Tiles);
+        let mut form = SettingsForm::new(&world, &ViewLimits::of(&world, &DesignWorld::new(10, 10)));
+        form.values = values.iter().map(|value| value.to_string()).collect();
+        return form;
+    }
+
+    #[test]
+    fn test_form_starts_from_the_current_values() {
+        let world = World::new(250, 200, 75, 50, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let form = SettingsForm::new(&world, &ViewLimits::of(&world, &DesignWorld::new(125, 50)));
+        assert_eq!(form.values, vec!["250", "200", "75", "50", "36", "125", "50"]);
+    }
+
+    #[test]
+    fn test_form_starts_from_the_limits_not_the_fitted_sizes() {
+        let mut world = World::new(250, 200, 75, 50, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let mut design_world = DesignWorld::new(125, 50);
+        let view_limits = ViewLimits::of(&world, &design_world);
+        view_limits.fit((80, 30), 9, 16, &mut world, &mut design_world);
+
+        let form = SettingsForm::new(&world, &view_limits);
+        assert_eq!(form.values, vec!["250", "200", "75", "50", "36", "125", "50"]);
+    }
+
+    #[test]
+    fn test_editing_the_selected_field() {
+        let mut form = form_with(["10", "10", "5", "5", "0", "10", "10"]);
+        form.move_selection(-1);
+        form.pop_char();
+        form.push_char('x');
+        form.push_char('2');
+        assert_eq!(form.values[6], "12");
+        form.move_selection(1);
+        assert_eq!(form.selected, 0);
+    }
+
+    #[test_case(["", "10", "5", "5", "0", "10", "10"], "World width needs a number" ; "empty field")]
+    #[test_case(["10", "10", "5", "5", "0", "2", "10"], "Design map must be at least 3x3" ; "tiny design map")]
+    #[test_case(["8", "10", "5", "5", "0", "10", "10"], "World must be at least as large as the design map" ; "design map larger than world")]
+    #[test_case(["10", "10", "0", "5", "0", "10", "10"], "Chunk must be at least 1x1" ; "empty chunk")]
+    #[test_case(["10", "10", "5", "11", "0", "10", "10"], "Chunk can't be larger than the world" ; "chunk larger than world")]
+    #[test_case(["10", "10", "5", "5", "0", "65539", "10"], "Design map width must be at most 65535" ; "design map wider than a u16")]
+    #[test_case(["10", "10", "5", "5", "0", "10", "999999"], "Design map height must be at most 65535" ; "design map taller than a u16")]
+    #[test_case(["5000", "10", "5", "5", "0", "10", "10"], "World can be at most 4096x4096" ; "world too wide")]
+    #[test_case(["10", "999999", "5", "5", "0", "10", "10"], "World can be at most 4096x4096" ; "world too tall")]
+    #[test_case(["10", "10", "5", "5", "2001", "10", "10"], "Frame interval can be at most 2000 ms" ; "interval too slow")]
+    fn test_invalid_settings(values: [&str; 7], expected: &str) {
+        assert_eq!(form_with(values).validate(), Err(expected.to_string()));
+    }
+
+    #[test_case(StorageKind::Tiles ; "tiles")]
+    #[test_case(StorageKind::BitPacked ; "bit packed")]
+    fn test_apply_resizes_and_keeps_cells_that_fit(storage_kind: StorageKind) {
+        let mut world = World::new(20, 20, 10, 10, 36, 0, Rule::conway(), storage_kind);
+        world.insert_live_cell((2, 3));
+        world.insert_live_cell((15, 4));
+        world.viewport_origin = (9, 9);
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.marked_positions.insert((2, 2), true);
+        design_world.marked_positions.insert((12, 2), true);
+        design_world.current_position = (15, 15);
+
+        let mut form = form_with(["12", "30", "6", "6", "50", "12", "12"]);
+        assert!(form.apply(&mut world, &mut design_world));
+
+        assert_eq!((world.x_size, world.y_size), (12, 30));
+        assert_eq!((world.chunk_x_size, world.chunk_y_size), (6, 6));
+        assert_eq!(world.frame_interval_ms, 50);
+        assert_eq!(world.live_cells(), vec![(2, 3)]);
+        assert_eq!(world.viewport_origin, (6, 9));
+        assert_eq!((design_world.max_x, design_world.max_y), (12, 12));
+        assert!(design_world.marked_positions[&(2, 2)]);
+        assert!(!design_world.marked_positions.contains_key(&(12, 2)));
+        assert_eq!(design_world.current_position, (10, 10));
+    }
+
+    #[test]
+    fn test_failed_apply_keeps_the_error_and_the_world() {
+        let mut world = World::new(20, 20, 10, 10, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let mut design_world = DesignWorld::new(20, 20);
+        let mut form = form_with(["10", "10", "20", "5", "0", "10", "10"]);
+
+        assert!(!form.apply(&mut world, &mut design_world));
+        assert!(form.error.is_some());
+        assert_eq!(world.x_size, 20);
+    }
+}
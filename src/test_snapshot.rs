@@ -0,0 +1,190 @@
+#[cfg(test)]
+pub mod test_snapshots {
+    use test_case::test_case;
+    use crate::boundary::BoundaryMode;
+    use crate::design_world::DesignWorld;
+    use crate::random::SeededRandom;
+    use crate::rule::Rule;
+    use crate::snapshot::{Snapshot, SnapshotError};
+    use crate::view_limits::ViewLimits;
+    use crate::world::{StorageKind, World};
+
+    fn running_world() -> (World, DesignWorld) {
+        let mut world = World::new(40, 30, 20, 10, 50, 0, Rule::parse("B36/S23").unwrap(), StorageKind::BitPacked);
+        world.boundary_mode = BoundaryMode::Torus;
+        for position in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            world.insert_live_cell(position);
+        }
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for _ in 0..9 {
+            world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+        world.viewport_origin = (13, 7);
+
+        let mut design_world = DesignWorld::new(20, 12);
+        design_world.marked_positions.insert((3, 4), true);
+        design_world.current_position = (5, 6);
+        return (world, design_world);
+    }
+
+    #[test]
+    fn test_restore_resumes_the_run() {
+        let (world, design_world) = running_world();
+        let text = Snapshot::capture(&world, &design_world, &ViewLimits::of(&world, &design_world)).to_text();
+
+        let mut restored_world = World::new(10, 10, 10, 10, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let mut restored_design = DesignWorld::new(10, 10);
+        Snapshot::parse(&text).unwrap().restore(&mut restored_world, &mut restored_design);
+
+        assert_eq!((restored_world.x_size, restored_world.y_size), (40, 30));
+        assert_eq!((restored_world.chunk_x_size, restored_world.chunk_y_size), (20, 10));
+        assert_eq!(restored_world.frame_interval_ms, 50);
+        assert_eq!(restored_world.rule, world.rule);
+        assert_eq!(restored_world.boundary_mode, BoundaryMode::Torus);
+        assert_eq!(restored_world.storage_kind, StorageKind::BitPacked);
+        assert_eq!(restored_world.current_generation, 10);
+        assert_eq!(restored_world.viewport_origin, (13, 7));
+        let mut expected_cells = world.live_cells();
+        let mut restored_cells = restored_world.live_cells();
+        expected_cells.sort();
+        restored_cells.sort();
+        assert_eq!(restored_cells, expected_cells);
+        assert_eq!((restored_design.max_x, restored_design.max_y), (20, 12));
+        assert_eq!(restored_design.current_position, (5, 6));
+        assert_eq!(restored_design.marked_cells(), vec![(3, 4)]);
+    }
+
+    #[test]
+    fn test_capture_text_round_trips() {
+        let (world, design_world) = running_world();
+        let snapshot = Snapshot::capture(&world, &design_world, &ViewLimits::of(&world, &design_world));
+        assert_eq!(Snapshot::parse(&snapshot.to_text()).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_capture_keeps_the_sizes_asked_for_and_marks_set_aside() {
+        let (mut world, mut design_world) = running_world();
+        design_world.marked_positions.insert((15, 9), true);
+        let view_limits = ViewLimits::of(&world, &design_world);
+        view_limits.fit((12, 12), 2, 2, &mut world, &mut design_world);
+        assert_eq!((world.chunk_x_size, design_world.max_x), (12, 12));
+        assert_eq!(design_world.clipped_marks, vec![(15, 9)]);
+
+        let snapshot = Snapshot::capture(&world, &design_world, &view_limits);
+        assert_eq!((snapshot.chunk_x_size, snapshot.chunk_y_size), (20, 10));
+        assert_eq!(snapshot.design_size, (20, 12));
+        assert_eq!(snapshot.design_marks, vec![(3, 4), (15, 9)]);
+    }
+
+    #[test_case(StorageKind::Tiles ; "tiles")]
+    #[test_case(StorageKind::BitPacked ; "bit packed")]
+    #[test_case(StorageKind::Unbounded ; "unbounded")]
+    fn test_generations_dying_cells_round_trip(storage_kind: StorageKind) {
+        let brians_brain = Rule::parse("/2/3").unwrap();
+        let mut world = World::new(30, 30, 30, 30, 36, 0, brians_brain, storage_kind);
+        let mut random = SeededRandom::new(5);
+        for y in 10..20 {
+            for x in 10..20 {
+                if random.chance(0.4) {
+                    world.insert_live_cell((x, y));
+                }
+            }
+        }
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for _ in 0..3 {
+            world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+        assert!(!world.dying_cells().is_empty());
+
+        let design_world = DesignWorld::new(10, 10);
+        let text = Snapshot::capture(&world, &design_world, &ViewLimits::of(&world, &design_world)).to_text();
+        let mut restored_world = World::new(10, 10, 10, 10, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let mut restored_design = DesignWorld::new(10, 10);
+        Snapshot::parse(&text).unwrap().restore(&mut restored_world, &mut restored_design);
+
+        // A step after restoring shows the dying cells still block births.
+        for step in 0..2 {
+            for y in 0..30 {
+                for x in 0..30 {
+                    assert_eq!(restored_world.cell_state(x, y), world.cell_state(x, y), "({}, {}) after {} steps", x, y, step);
+                }
+            }
+            world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            restored_world.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+    }
+
+    #[test_case("life 1\n" ; "wrong magic")]
+    #[test_case("" ; "empty file")]
+    fn test_not_a_snapshot(text: &str) {
+        assert!(matches!(Snapshot::parse(text), Err(SnapshotError::NotASnapshot)));
+    }
+
+    #[test]
+    fn test_newer_versions_are_rejected() {
+        let result = Snapshot::parse("conways-game-of-life-snapshot 4\n");
+        assert!(matches!(result, Err(SnapshotError::UnsupportedVersion(4))));
+    }
+
+    #[test]
+    fn test_version_1_viewport_is_a_chunk_index() {
+        let text = "conways-game-of-life-snapshot 1\nworld 40 30\nchunk 20 10\nrule B3/S23\nviewport 1 2\ndesign 10 10 1 1\ncells 0\n";
+        assert_eq!(Snapshot::parse(text).unwrap().viewport_origin, (20, 20));
+    }
+
+    #[test_case("world 10 10\nchunk 5 5\nrule B3/S23\ndesign 10 10 1 1\n", "cells" ; "no cells")]
+    #[test_case("chunk 5 5\nrule B3/S23\ndesign 10 10 1 1\ncells 0\n", "world" ; "no world")]
+    fn test_missing_sections(body: &str, expected: &str) {
+        let text = format!("conways-game-of-life-snapshot 1\n{}", body);
+        match Snapshot::parse(&text) {
+            Err(SnapshotError::Missing(key)) => assert_eq!(key, expected),
+            other => panic!("expected a missing section, got {:?}", other),
+        }
+    }
+
+    #[test_case("world 10 ten\n", 2 ; "bad number")]
+    #[test_case("world 10 10\nboundary mobius\n", 3 ; "bad boundary")]
+    #[test_case("world 10 10\ncells 2\n1 1\n", 3 ; "too few cells")]
+    #[test_case("world 10 10\ncells 1\n1 1 1\n", 4 ; "bad cell")]
+    #[test_case("world 10 10\ndying 1\n1 1\n", 4 ; "dying cell without a state")]
+    fn test_parse_errors_name_the_line(body: &str, expected_line: usize) {
+        let text = format!("conways-game-of-life-snapshot 1\n{}", body);
+        match Snapshot::parse(&text) {
+            Err(SnapshotError::Parse { line, .. }) => assert_eq!(line, expected_line),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test_case("B3/S23", 2 ; "two state rule")]
+    #[test_case("/2/3", 3 ; "past the last dying state")]
+    #[test_case("/2/3", 1 ; "alive")]
+    fn test_dying_state_outside_the_rule_is_invalid(rule: &str, state: u8) {
+        let text = format!(
+            "conways-game-of-life-snapshot 3\nworld 10 10\nchunk 5 5\nrule {}\ndesign 10 10 1 1\ncells 0\ndying 1\n2 2 {}\n",
+            rule, state
+        );
+        assert!(matches!(Snapshot::parse(&text), Err(SnapshotError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_chunk_larger_than_world_is_invalid() {
+        let text = "conways-game-of-life-snapshot 1\nworld 10 10\nchunk 20 5\nrule B3/S23\ndesign 10 10 1 1\ncells 0\n";
+        assert!(matches!(Snapshot::parse(text), Err(SnapshotError::Invalid(_))));
+    }
+
+    #[test_case("world 100000 100000\n", "world width and height can be at most 4096" ; "world too large")]
+    #[test_case("world 10 5000\n", "world width and height can be at most 4096" ; "world too tall")]
+    #[test_case("world 10 10\ninterval 99999999\n", "frame interval in ms can be at most 2000" ; "interval too long")]
+    fn test_sizes_past_the_settings_limits_are_rejected(settings: &str, expected: &str) {
+        let text = format!(
+            "conways-game-of-life-snapshot 3\n{}chunk 5 5\nrule B3/S23\ndesign 10 10 1 1\ncells 0\n",
+            settings
+        );
+        match Snapshot::parse(&text) {
+            Err(error @ SnapshotError::TooLarge { .. }) => assert_eq!(error.to_string(), expected),
+            other => panic!("expected a size error, got {:?}", other),
+        }
+    }
+}
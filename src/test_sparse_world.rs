@@ -0,0 +1,48 @@
+#[cfg(test)]
+pub mod test_sparse {
+    use crate::rule::Rule;
+    use crate::sparse_world::SparseWorld;
+    use crate::world::{StorageKind, World};
+
+    #[test]
+    fn test_glider_walks_into_negative_coordinates() {
+        let mut sparse_world = SparseWorld::new();
+        // A glider heading up and to the left.
+        for key in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 2)] {
+            sparse_world.insert_position(key);
+        }
+
+        for _ in 0..400 {
+            sparse_world.handle_generation(&Rule::conway());
+        }
+
+        assert_eq!(sparse_world.population(), 5);
+        for key in [(-100, -100), (-99, -100), (-98, -100), (-100, -99), (-99, -98)] {
+            assert!(sparse_world.is_alive(key));
+        }
+    }
+
+    #[test]
+    fn test_unbounded_matches_tiles_away_from_the_edges() {
+        let r_pentomino = [(21, 20), (22, 20), (20, 21), (21, 21), (21, 22)];
+        let mut tiles = World::new(60, 60, 60, 60, 0, 0, Rule::conway(), StorageKind::Tiles);
+        let mut unbounded = World::new(60, 60, 60, 60, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        for key in r_pentomino {
+            tiles.insert_live_cell(key);
+            unbounded.insert_live_cell(key);
+        }
+
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        for _ in 0..30 {
+            tiles.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            unbounded.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+        }
+
+        let mut tiles_cells = tiles.live_cells();
+        let mut unbounded_cells = unbounded.live_cells();
+        tiles_cells.sort();
+        unbounded_cells.sort();
+        assert_eq!(tiles_cells, unbounded_cells);
+    }
+}
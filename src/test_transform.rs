@@ -0,0 +1,64 @@
+#[cfg(test)]
+pub mod test_transforms {
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::pattern_file::{live_cells, normalize, Pattern};
+    use crate::rle::read_rle;
+    use crate::transform::Transform;
+
+    /// An L of three cells down and one to the right, in a 2x3 box.
+    fn l_shape() -> Pattern {
+        return normalize(vec![(0, 0), (0, 1), (0, 2), (1, 2)]).unwrap();
+    }
+
+    #[test_case(Transform::RotateClockwise, vec![(0, 0), (1, 0), (2, 0), (0, 1)] ; "rotate clockwise")]
+    #[test_case(Transform::RotateCounterClockwise, vec![(2, 0), (0, 1), (1, 1), (2, 1)] ; "rotate counter clockwise")]
+    #[test_case(Transform::FlipHorizontal, vec![(1, 0), (1, 1), (0, 2), (1, 2)] ; "flip horizontal")]
+    #[test_case(Transform::FlipVertical, vec![(0, 0), (1, 0), (0, 1), (0, 2)] ; "flip vertical")]
+    #[test_case(Transform::Transpose, vec![(0, 0), (1, 0), (2, 0), (2, 1)] ; "transpose")]
+    fn test_l_shape(transform: Transform, expected: Vec<(u16, u16)>) {
+        assert_eq!(live_cells(&transform.apply(&l_shape())), expected);
+    }
+
+    #[test_case(Transform::RotateClockwise, 4 ; "four clockwise turns")]
+    #[test_case(Transform::RotateCounterClockwise, 4 ; "four counter clockwise turns")]
+    #[test_case(Transform::FlipHorizontal, 2 ; "two horizontal flips")]
+    #[test_case(Transform::FlipVertical, 2 ; "two vertical flips")]
+    #[test_case(Transform::Transpose, 2 ; "two transposes")]
+    fn test_repeating_returns_to_the_start(transform: Transform, times: usize) {
+        let (gun, _) = read_rle("24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+            2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!").unwrap();
+        let mut turned = gun.clone();
+        for _ in 0..times {
+            turned = transform.apply(&turned);
+        }
+        assert_eq!(live_cells(&turned), live_cells(&gun));
+    }
+
+    #[test]
+    fn test_transforming_a_paste_turns_the_clipboard_too() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.clipboard = Some(l_shape());
+        design_world.preview = Some(l_shape());
+        design_world.transform_preview(Transform::RotateClockwise, true);
+        design_world.current_position = (4, 4);
+        design_world.stamp_preview();
+
+        let expected = vec![(0, 0), (1, 0), (2, 0), (0, 1)];
+        assert_eq!(live_cells(design_world.clipboard.as_ref().unwrap()), expected);
+        let mut marked = design_world.marked_cells();
+        marked.sort_by_key(|position| (position.1, position.0));
+        assert_eq!(marked, vec![(4, 4), (5, 4), (6, 4), (4, 5)]);
+    }
+
+    #[test]
+    fn test_transforming_a_library_stamp_leaves_the_clipboard() {
+        let mut design_world = DesignWorld::new(20, 20);
+        design_world.clipboard = Some(l_shape());
+        design_world.preview = Some(l_shape());
+        design_world.transform_preview(Transform::Transpose, false);
+
+        assert_eq!(live_cells(design_world.clipboard.as_ref().unwrap()), live_cells(&l_shape()));
+        assert_eq!(live_cells(design_world.preview.as_ref().unwrap()), vec![(0, 0), (1, 0), (2, 0), (2, 1)]);
+    }
+}
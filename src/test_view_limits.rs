@@ -0,0 +1,52 @@
+#[cfg(test)]
+pub mod test_terminal_fit {
+    use test_case::test_case;
+    use crate::design_world::DesignWorld;
+    use crate::rule::Rule;
+    use crate::view_limits::ViewLimits;
+    use crate::world::{StorageKind, World};
+
+    const LIMITS: ViewLimits = ViewLimits {
+        chunk_x_size: 75,
+        chunk_y_size: 50,
+        design_x_size: 125,
+        design_y_size: 50,
+    };
+
+    #[test_case((200, 80), (75, 50) ; "large terminal keeps the limits")]
+    #[test_case((60, 30), (60, 21) ; "small terminal")]
+    #[test_case((0, 5), (1, 1) ; "at least one cell")]
+    fn test_fit_view(terminal_size: (u16, u16), expected: (usize, usize)) {
+        assert_eq!(LIMITS.fit_view(terminal_size.0, terminal_size.1, 9), expected);
+    }
+
+    #[test_case((200, 80), (125, 50) ; "large terminal keeps the limits")]
+    #[test_case((80, 40), (80, 24) ; "small terminal")]
+    #[test_case((2, 10), (3, 3) ; "never below three by three")]
+    fn test_fit_design(terminal_size: (u16, u16), expected: (u16, u16)) {
+        assert_eq!(LIMITS.fit_design(terminal_size.0, terminal_size.1, 16), expected);
+    }
+
+    #[test]
+    fn test_fit_sets_marks_aside_and_grows_back() {
+        let mut world = World::new(250, 250, 75, 50, 36, 0, Rule::conway(), StorageKind::Tiles);
+        let mut design_world = DesignWorld::new(125, 50);
+        design_world.marked_positions.insert((10, 5), true);
+        design_world.marked_positions.insert((100, 5), true);
+        let limits = ViewLimits::of(&world, &design_world);
+        assert_eq!(limits, LIMITS);
+
+        limits.fit((80, 40), 9, 16, &mut world, &mut design_world);
+        assert_eq!((world.chunk_x_size, world.chunk_y_size), (75, 31));
+        assert_eq!((design_world.max_x, design_world.max_y), (80, 24));
+        assert_eq!(design_world.marked_cells(), vec![(10, 5)]);
+
+        limits.fit((300, 100), 9, 16, &mut world, &mut design_world);
+        assert_eq!((world.chunk_x_size, world.chunk_y_size), (75, 50));
+        assert_eq!((design_world.max_x, design_world.max_y), (125, 50));
+        let mut marks = design_world.marked_cells();
+        marks.sort();
+        assert_eq!(marks, vec![(10, 5), (100, 5)]);
+        assert!(design_world.clipped_marks.is_empty());
+    }
+}
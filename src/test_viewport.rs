@@ -0,0 +1,91 @@
+#[cfg(test)]
+pub mod test_game_viewport {
+    use test_case::test_case;
+    use crate::rule::Rule;
+    use crate::world::{StorageKind, World};
+    use crate::world_display::MoveDirections;
+    use crate::zoom::ZoomLevel;
+
+    fn world() -> World {
+        return World::new(100, 60, 20, 20, 0, 0, Rule::conway(), StorageKind::Tiles);
+    }
+
+    #[test_case((7, 3), (7, 3) ; "inside")]
+    #[test_case((90, 90), (80, 40) ; "clamped to the far edges")]
+    #[test_case((-3, -1), (0, 0) ; "clamped to the origin")]
+    fn test_pan_stays_inside_a_bounded_world(cells: (i64, i64), expected: (i64, i64)) {
+        let mut world = world();
+        world.pan(cells.0, cells.1);
+        assert_eq!(world.viewport_origin, expected);
+    }
+
+    #[test]
+    fn test_unbounded_pan_is_free() {
+        let mut world = World::new(100, 60, 20, 20, 0, 0, Rule::conway(), StorageKind::Unbounded);
+        world.move_viewport(MoveDirections::Left, 35);
+        world.move_viewport(MoveDirections::Down, 2);
+        assert_eq!(world.viewport_origin, (-35, -2));
+    }
+
+    #[test]
+    fn test_zooming_out_pulls_the_view_back_inside() {
+        let mut world = world();
+        world.pan(80, 40);
+        world.zoom_out();
+        assert_eq!(world.viewport_size(), (20, 40));
+        assert_eq!(world.viewport_origin, (80, 20));
+    }
+
+    #[test]
+    fn test_centre_on_population() {
+        let mut world = world();
+        assert!(!world.centre_on_population());
+        world.insert_live_cell((40, 30));
+        world.insert_live_cell((50, 34));
+        assert_eq!(world.bounding_box(), Some(((40, 30), (50, 34))));
+
+        assert!(world.centre_on_population());
+        assert_eq!(world.viewport_origin, (35, 22));
+    }
+
+    #[test]
+    fn test_centre_on_a_corner_is_clamped() {
+        let mut world = world();
+        world.centre_on((99, 0));
+        assert_eq!(world.viewport_origin, (80, 0));
+    }
+
+    #[test]
+    fn test_zoom_walks_through_every_level_and_back() {
+        let mut world = world();
+        world.zoom_in();
+        assert_eq!(world.zoom, ZoomLevel::Cells);
+
+        let mut levels = vec![world.zoom];
+        for _ in 0..8 {
+            world.zoom_out();
+            levels.push(world.zoom);
+        }
+        assert_eq!(levels[1..5], [ZoomLevel::HalfBlocks, ZoomLevel::Quadrants, ZoomLevel::Braille, ZoomLevel::Density(4)]);
+        assert_eq!(world.zoom, ZoomLevel::Density(16));
+
+        for _ in 0..8 {
+            world.zoom_in();
+        }
+        assert_eq!(world.zoom, ZoomLevel::Cells);
+    }
+
+    #[test_case(ZoomLevel::HalfBlocks, vec![true, false], '▀' ; "upper half")]
+    #[test_case(ZoomLevel::HalfBlocks, vec![false, true], '▄' ; "lower half")]
+    #[test_case(ZoomLevel::Quadrants, vec![true, false, false, true], '▚' ; "quadrant diagonal")]
+    #[test_case(ZoomLevel::Quadrants, vec![false; 4], ' ' ; "empty quadrants")]
+    #[test_case(ZoomLevel::Braille, vec![true, false, false, false, false, false, false, false], '⠁' ; "braille top left dot")]
+    #[test_case(ZoomLevel::Braille, vec![false, false, false, false, false, false, false, true], '⢀' ; "braille bottom right dot")]
+    #[test_case(ZoomLevel::Braille, vec![true; 8], '⣿' ; "braille full")]
+    #[test_case(ZoomLevel::Density(4), [vec![true; 1], vec![false; 15]].concat(), '░' ; "sparse shade")]
+    #[test_case(ZoomLevel::Density(4), [vec![true; 8], vec![false; 8]].concat(), '▒' ; "half shade")]
+    #[test_case(ZoomLevel::Density(4), vec![true; 16], '█' ; "full shade")]
+    fn test_glyph(zoom: ZoomLevel, alive: Vec<bool>, expected: char) {
+        assert_eq!(zoom.glyph(&alive), expected);
+    }
+}
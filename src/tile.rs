@@ -1,15 +1,28 @@
+pub const DEAD: u8 = 0;
+pub const ALIVE: u8 = 1;
+
+/// A single cell, `state` is `DEAD`, `ALIVE`, or from 2 upwards one of the dying states
+/// cells fade through under Generations rules.
 pub struct Tile {
-    pub alive: bool,
+    pub state: u8,
 }
 
 impl Tile {
     pub fn default() -> Tile {
         return Tile {
-            alive: false,
+            state: DEAD,
         };
     }
 
+    pub fn is_alive(&self) -> bool {
+        return self.state == ALIVE;
+    }
+
     pub fn set_alive(&mut self, value: bool) {
-        self.alive = value;
+        self.state = if value { ALIVE } else { DEAD };
+    }
+
+    pub fn set_state(&mut self, state: u8) {
+        self.state = state;
     }
 }
@@ -0,0 +1,38 @@
+use crate::pattern_file::{pattern_size, Pattern};
+
+/// The ways a pattern can be turned over before it's stamped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    RotateClockwise,
+    RotateCounterClockwise,
+    /// Mirrors left to right.
+    FlipHorizontal,
+    /// Mirrors top to bottom.
+    FlipVertical,
+    /// Swaps x and y, mirroring along the top left to bottom right diagonal.
+    Transpose,
+}
+
+impl Transform {
+    /// Applies the transform inside the box from `(0, 0)` to the furthest live cell, so the
+    /// result still starts at `(0, 0)`.
+    pub fn apply(&self, pattern: &Pattern) -> Pattern {
+        let (width, height) = pattern_size(pattern);
+        let last_x = width.saturating_sub(1) as u16;
+        let last_y = height.saturating_sub(1) as u16;
+        return pattern
+            .iter()
+            .filter(|(_, alive)| **alive)
+            .map(|((x, y), alive)| {
+                let position = match self {
+                    Transform::RotateClockwise => (last_y - y, *x),
+                    Transform::RotateCounterClockwise => (*y, last_x - x),
+                    Transform::FlipHorizontal => (last_x - x, *y),
+                    Transform::FlipVertical => (*x, last_y - y),
+                    Transform::Transpose => (*y, *x),
+                };
+                (position, *alive)
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,66 @@
+use crate::design_world::DesignWorld;
+use crate::world::World;
+
+/// The smallest design map that still has a cell inside its border.
+const MIN_DESIGN_SIZE: u16 = 3;
+
+/// The game view and design map sizes asked for on the command line or the settings
+/// screen. A terminal too small for them only gets as much as fits, and they grow back
+/// when it's enlarged again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewLimits {
+    pub chunk_x_size: usize,
+    pub chunk_y_size: usize,
+    pub design_x_size: u16,
+    pub design_y_size: u16,
+}
+
+impl ViewLimits {
+    /// Takes the current sizes as the limits.
+    pub fn of(world: &World, design_world: &DesignWorld) -> ViewLimits {
+        return ViewLimits {
+            chunk_x_size: world.chunk_x_size,
+            chunk_y_size: world.chunk_y_size,
+            design_x_size: design_world.max_x,
+            design_y_size: design_world.max_y,
+        };
+    }
+
+    /// The game view that fits a `columns` x `rows` terminal below `reserved_rows` lines of
+    /// header and status, at least a single cell.
+    pub fn fit_view(&self, columns: u16, rows: u16, reserved_rows: usize) -> (usize, usize) {
+        let rows = (rows as usize).saturating_sub(reserved_rows);
+        return (
+            self.chunk_x_size.min(columns as usize).max(1),
+            self.chunk_y_size.min(rows).max(1),
+        );
+    }
+
+    /// The design map that fits the same way, never below 3x3 so there's somewhere to draw.
+    pub fn fit_design(&self, columns: u16, rows: u16, reserved_rows: usize) -> (u16, u16) {
+        let rows = (rows as usize).saturating_sub(reserved_rows).min(u16::MAX as usize) as u16;
+        return (
+            self.design_x_size.min(columns).max(MIN_DESIGN_SIZE),
+            self.design_y_size.min(rows).max(MIN_DESIGN_SIZE),
+        );
+    }
+
+    /// Resizes the game view and the design map to fit the terminal, keeping every mark that
+    /// still fits inside the design map's border.
+    pub fn fit(
+        &self,
+        terminal_size: (u16, u16),
+        game_reserved_rows: usize,
+        design_reserved_rows: usize,
+        world: &mut World,
+        design_world: &mut DesignWorld,
+    ) {
+        let (columns, rows) = terminal_size;
+        let (chunk_x_size, chunk_y_size) = self.fit_view(columns, rows, game_reserved_rows);
+        world.resize_viewport(chunk_x_size, chunk_y_size);
+        let (design_x_size, design_y_size) = self.fit_design(columns, rows, design_reserved_rows);
+        if (design_x_size, design_y_size) != (design_world.max_x, design_world.max_y) {
+            design_world.resize(design_x_size, design_y_size);
+        }
+    }
+}
@@ -1,53 +1,127 @@
 use std::collections::HashMap;
-use crate::tile::Tile;
+use std::time::{Duration, Instant};
+use crate::bit_grid::BitGrid;
+use crate::boundary::BoundaryMode;
+use crate::chunk_stripe::ChunkStripe;
+use crate::hashlife::{HashLife, MAX_GENERATIONS, MAX_STEP_LOG};
+use crate::rule::Rule;
+use crate::sparse_world::SparseWorld;
+use crate::tile::{Tile, ALIVE, DEAD};
 use crate::world_display::MoveDirections;
-use std::collections::hash_map::Iter;
+use crate::zoom::ZoomLevel;
 
-#[allow(dead_code)]
-pub enum OscillatorOrientation {
-    Vertical,
-    Horizontal,
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+/// Which structure holds the cells of a `World`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageKind {
+    /// One `Tile` per cell of the `x_size` x `y_size` grid.
+    Tiles,
+    /// The `x_size` x `y_size` grid packed 64 cells to a `u64` word, stepped with bitwise logic.
+    BitPacked,
+    /// Only live cells, on a plane with no edges. `x_size` and `y_size` only describe
+    /// the home area the design map is copied into.
+    Unbounded,
+}
+
+impl StorageKind {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            StorageKind::Tiles => "tiles",
+            StorageKind::BitPacked => "bits",
+            StorageKind::Unbounded => "unbounded",
+        };
+    }
+
+    /// The storage a `name()` came from.
+    pub fn from_name(name: &str) -> Option<StorageKind> {
+        return match name {
+            "tiles" => Some(StorageKind::Tiles),
+            "bits" => Some(StorageKind::BitPacked),
+            "unbounded" => Some(StorageKind::Unbounded),
+            _ => None,
+        };
+    }
 }
 
 pub struct World {
     pub tiles: HashMap<(usize, usize), Tile>,
     pub alive_tile_keys: HashMap<(usize, usize), bool>,
+    pub dying_tile_keys: HashMap<(usize, usize), bool>,
     pub x_size: usize,
     pub y_size: usize,
     pub chunk_x_size: usize,
     pub chunk_y_size: usize,
-    pub current_chunk_address: (i32, i32),
+    /// The cell shown in the top left corner of the game view.
+    pub viewport_origin: (i64, i64),
+    /// Cells the view moves per pan key press.
+    pub pan_step: usize,
     pub allotted_read_input_time: usize,
     pub frame_interval_ms: usize,
+    pub rule: Rule,
+    pub boundary_mode: BoundaryMode,
+    pub storage_kind: StorageKind,
+    pub sparse_world: SparseWorld,
+    pub bit_grid: BitGrid,
+    pub current_generation: u128,
+    /// Threads used to step tiles, anything above 1 steps busy worlds one stripe per thread.
+    pub worker_threads: usize,
+    /// How many cells the game view draws in each terminal character.
+    pub zoom: ZoomLevel,
+    /// Generations only advance by single steps or the advance prompt while paused.
+    pub paused: bool,
+    /// Generations still to run from the advance prompt or a fast forward on stepped
+    /// storage, worked through a batch per frame so the view keeps drawing and Esc can stop them.
+    pub pending_generations: u128,
+    /// The HashLife the last jump left behind, kept while nothing else touches the cells so
+    /// the next jump reuses its memoized steps.
+    pub hashlife: Option<HashLife>,
+    /// `f` jumps `2^jump_exponent` generations.
+    pub jump_exponent: u32,
+    /// Steps as fast as possible, ignoring `frame_interval_ms` until it's turned off.
+    pub uncapped: bool,
 }
 
+pub const MAX_FRAME_INTERVAL_MS: usize = 2000;
+/// Widest or tallest world the command line and settings screen accept, tile storage
+/// keeps an entry for every cell so this bounds its memory.
+pub const MAX_WORLD_SIZE: usize = 4096;
+pub const DEFAULT_PAN_STEP: usize = 8;
+const DEFAULT_JUMP_EXPONENT: u32 = 10;
+const MAX_JUMP_EXPONENT: u32 = MAX_STEP_LOG;
+/// Worlds smaller than this step serially, threads cost more than they save.
+const PARALLEL_MIN_CELLS: usize = 64 * 64;
+/// The parallel path looks at every cell while the serial one only visits live cells and
+/// their neighbors, so it's only used once at least one cell in this many is occupied.
+const PARALLEL_MAX_CELLS_PER_OCCUPIED: usize = 16;
+
 impl World {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(x_size: usize,
                y_size: usize,
                chunk_x_size: usize,
                chunk_y_size: usize,
                frame_interval_ms: usize,
-               allotted_read_input_time: usize
+               allotted_read_input_time: usize,
+               rule: Rule,
+               storage_kind: StorageKind,
     ) -> World {
-        let mut tiles = HashMap::new();
-        let alive_tile_keys = HashMap::new();
-        for y in 0..y_size {
-            for x in 0..x_size {
-                let tile = Tile::default();
-                tiles.insert((x, y), tile);
-            }
-        }
-
         let is_chunk_1_by_1 = x_size / chunk_x_size == 1 && y_size / chunk_y_size == 1;
 
-        return World {
+        let mut world = World {
             x_size,
             y_size,
-            tiles,
+            tiles: HashMap::new(),
             chunk_x_size,
             chunk_y_size,
-            alive_tile_keys,
-            current_chunk_address: (0, 0),
+            alive_tile_keys: HashMap::new(),
+            dying_tile_keys: HashMap::new(),
+            viewport_origin: (0, 0),
+            pan_step: DEFAULT_PAN_STEP,
             allotted_read_input_time: (
                 if is_chunk_1_by_1 {
                     if allotted_read_input_time == 0 {
@@ -64,16 +138,220 @@ impl World {
                 }
             ),
             frame_interval_ms,
+            rule,
+            boundary_mode: BoundaryMode::Dead,
+            storage_kind,
+            sparse_world: SparseWorld::new(),
+            bit_grid: BitGrid::new(0, 0),
+            current_generation: 1,
+            worker_threads: 1,
+            zoom: ZoomLevel::default(),
+            paused: false,
+            pending_generations: 0,
+            hashlife: None,
+            jump_exponent: DEFAULT_JUMP_EXPONENT,
+            uncapped: false,
         };
+        // Allocates whichever storage was picked.
+        world.reset_world();
+        return world;
     }
 
     pub fn reset_world(&mut self) {
+        self.pending_generations = 0;
+        self.hashlife = None;
         self.tiles.clear();
         self.alive_tile_keys.clear();
-        for y in 0..self.y_size {
-            for x in 0..self.x_size {
-                self.tiles.insert((x, y), Tile::default());
+        self.dying_tile_keys.clear();
+        self.sparse_world.alive_tile_keys.clear();
+        self.sparse_world.dying_tile_keys.clear();
+        match self.storage_kind {
+            StorageKind::Tiles => {
+                for y in 0..self.y_size {
+                    for x in 0..self.x_size {
+                        self.tiles.insert((x, y), Tile::default());
+                    }
+                }
+                self.bit_grid = BitGrid::new(0, 0);
             }
+            StorageKind::BitPacked => self.bit_grid = BitGrid::new(self.x_size, self.y_size),
+            StorageKind::Unbounded => self.bit_grid = BitGrid::new(0, 0),
+        }
+    }
+
+    /// The `Tile` state at a position, dead outside a bounded world.
+    pub fn cell_state(&self, x: i64, y: i64) -> u8 {
+        if self.storage_kind == StorageKind::Unbounded {
+            return self.sparse_world.cell_state((x, y));
+        }
+        if x < 0 || y < 0 {
+            return DEAD;
+        }
+        return match self.storage_kind {
+            StorageKind::BitPacked => self.bit_grid.cell_state(x as usize, y as usize),
+            _ => self.tiles.get(&(x as usize, y as usize)).map_or(DEAD, |tile| tile.state),
+        };
+    }
+
+    pub fn population(&self) -> usize {
+        return match self.storage_kind {
+            StorageKind::Tiles => self.alive_tile_keys.len(),
+            StorageKind::BitPacked => self.bit_grid.population(),
+            StorageKind::Unbounded => self.sparse_world.population(),
+        };
+    }
+
+    /// Every live cell, in no particular order.
+    pub fn live_cells(&self) -> Vec<(i64, i64)> {
+        return match self.storage_kind {
+            StorageKind::Tiles => self
+                .alive_tile_keys
+                .keys()
+                .map(|key| (key.0 as i64, key.1 as i64))
+                .collect(),
+            StorageKind::BitPacked => self
+                .bit_grid
+                .live_cells()
+                .into_iter()
+                .map(|key| (key.0 as i64, key.1 as i64))
+                .collect(),
+            StorageKind::Unbounded => self.sparse_world.alive_tile_keys.iter().copied().collect(),
+        };
+    }
+
+    /// Every cell fading through a Generations dying state, with that state.
+    pub fn dying_cells(&self) -> Vec<((i64, i64), u8)> {
+        return match self.storage_kind {
+            StorageKind::Tiles => self
+                .dying_tile_keys
+                .keys()
+                .map(|key| ((key.0 as i64, key.1 as i64), self.tiles[key].state))
+                .collect(),
+            StorageKind::BitPacked => self
+                .bit_grid
+                .dying_cells()
+                .into_iter()
+                .map(|(key, state)| ((key.0 as i64, key.1 as i64), state))
+                .collect(),
+            StorageKind::Unbounded => self
+                .sparse_world
+                .dying_tile_keys
+                .iter()
+                .map(|(key, state)| (*key, *state))
+                .collect(),
+        };
+    }
+
+    /// Puts a cell into a dying `state`, dropped like `insert_live_cell` outside a bounded
+    /// world.
+    pub fn insert_dying_cell(&mut self, key: (i64, i64), state: u8) {
+        if self.storage_kind == StorageKind::Unbounded {
+            self.hashlife = None;
+            self.sparse_world.alive_tile_keys.remove(&key);
+            self.sparse_world.dying_tile_keys.insert(key, state);
+            return;
+        }
+        if key.0 < 0
+            || key.1 < 0
+            || (key.0 as usize) >= self.x_size
+            || (key.1 as usize) >= self.y_size
+        {
+            return;
+        }
+        let key = (key.0 as usize, key.1 as usize);
+        if self.storage_kind == StorageKind::BitPacked {
+            self.bit_grid.set_dying(key.0, key.1, state);
+        } else {
+            self.set_tile_state(key, state);
+        }
+    }
+
+    /// Marks a cell as alive, silently dropping it when it lies outside a bounded world.
+    pub fn insert_live_cell(&mut self, key: (i64, i64)) {
+        if self.storage_kind == StorageKind::Unbounded {
+            self.hashlife = None;
+            self.sparse_world.insert_position(key);
+            return;
+        }
+        if key.0 < 0
+            || key.1 < 0
+            || (key.0 as usize) >= self.x_size
+            || (key.1 as usize) >= self.y_size
+        {
+            return;
+        }
+        let key = (key.0 as usize, key.1 as usize);
+        if self.storage_kind == StorageKind::BitPacked {
+            self.bit_grid.set_alive(key.0, key.1, true);
+        } else {
+            World::insert_position(&mut self.tiles, &mut self.alive_tile_keys, key);
+        }
+    }
+
+    /// Moves the live cells into a different storage, cells outside the `x_size` x `y_size`
+    /// area are lost when switching from an unbounded world back to tiles, and dying cells
+    /// are cleared.
+    pub fn set_storage_kind(&mut self, storage_kind: StorageKind) {
+        if self.storage_kind == storage_kind {
+            return;
+        }
+        let live_cells = self.live_cells();
+        self.storage_kind = storage_kind;
+        self.reset_world();
+        for key in live_cells {
+            self.insert_live_cell(key);
+        }
+        self.clamp_viewport();
+    }
+
+    /// Replaces every cell with `cells`, moving a bounded world onto the unbounded plane
+    /// when some of them don't fit so none are lost.
+    pub fn replace_live_cells(&mut self, cells: Vec<(i64, i64)>) {
+        let fits = cells.iter().all(|(x, y)| {
+            *x >= 0 && *y >= 0 && (*x as u64) < self.x_size as u64 && (*y as u64) < self.y_size as u64
+        });
+        if !fits && self.storage_kind != StorageKind::Unbounded {
+            self.storage_kind = StorageKind::Unbounded;
+            self.viewport_origin = (0, 0);
+        }
+        self.reset_world();
+        for key in cells {
+            self.insert_live_cell(key);
+        }
+    }
+
+    /// Changes the world and chunk sizes, keeping the live cells that still fit. Dying
+    /// cells are cleared like when switching storage.
+    pub fn resize(
+        &mut self,
+        x_size: usize,
+        y_size: usize,
+        chunk_x_size: usize,
+        chunk_y_size: usize,
+    ) {
+        let live_cells = self.live_cells();
+        self.x_size = x_size;
+        self.y_size = y_size;
+        self.chunk_x_size = chunk_x_size;
+        self.chunk_y_size = chunk_y_size;
+        self.reset_world();
+        for key in live_cells {
+            self.insert_live_cell(key);
+        }
+        self.clamp_viewport();
+    }
+
+    fn set_tile_state(&mut self, key: (usize, usize), state: u8) {
+        self.tiles.get_mut(&key).unwrap().set_state(state);
+        if state == ALIVE {
+            self.alive_tile_keys.insert(key, true);
+        } else {
+            self.alive_tile_keys.remove(&key);
+        }
+        if state > ALIVE {
+            self.dying_tile_keys.insert(key, true);
+        } else {
+            self.dying_tile_keys.remove(&key);
         }
     }
 
@@ -86,237 +364,257 @@ impl World {
         alive_tile_keys.insert(key, true);
     }
 
-    pub fn move_chunk(&mut self, move_direction: MoveDirections) {
-        let mut adjustment_amount: (i32, i32) = (0, 0);
+    /// Moves the view `distance` cells, `pan_step` for the movement keys or a whole view
+    /// for a page jump.
+    pub fn move_viewport(&mut self, move_direction: MoveDirections, distance: i64) {
         match move_direction {
-            MoveDirections::Left => {
-                adjustment_amount = (adjustment_amount.0 - 1, adjustment_amount.1)
-            }
-            MoveDirections::Right => {
-                adjustment_amount = (adjustment_amount.0 + 1, adjustment_amount.1)
-            }
-            MoveDirections::Up => {
-                adjustment_amount = (adjustment_amount.0, adjustment_amount.1 + 1)
-            }
-            MoveDirections::Down => {
-                adjustment_amount = (adjustment_amount.0, adjustment_amount.1 - 1)
-            }
+            MoveDirections::Left => self.pan(-distance, 0),
+            MoveDirections::Right => self.pan(distance, 0),
+            MoveDirections::Up => self.pan(0, distance),
+            MoveDirections::Down => self.pan(0, -distance),
+        }
+    }
+
+    pub fn pan(&mut self, cells_x: i64, cells_y: i64) {
+        self.viewport_origin.0 += cells_x;
+        self.viewport_origin.1 += cells_y;
+        self.clamp_viewport();
+    }
+
+    /// Changes how many characters the game view is across and down, within the world.
+    pub fn resize_viewport(&mut self, chunk_x_size: usize, chunk_y_size: usize) {
+        self.chunk_x_size = chunk_x_size.clamp(1, self.x_size);
+        self.chunk_y_size = chunk_y_size.clamp(1, self.y_size);
+        self.clamp_viewport();
+    }
+
+    /// The width and height in cells of what the game view shows at the current zoom.
+    pub fn viewport_size(&self) -> (i64, i64) {
+        let (char_width, char_height) = self.zoom.cells_per_char();
+        return (
+            (self.chunk_x_size * char_width) as i64,
+            (self.chunk_y_size * char_height) as i64,
+        );
+    }
+
+    /// Moves the view so `position` is in the middle of it.
+    pub fn centre_on(&mut self, position: (i64, i64)) {
+        let (width, height) = self.viewport_size();
+        self.viewport_origin = (position.0 - width / 2, position.1 - height / 2);
+        self.clamp_viewport();
+    }
+
+    /// Centres the view on the middle of the live cells, returns false when there are none.
+    pub fn centre_on_population(&mut self) -> bool {
+        let Some((top_left, bottom_right)) = self.bounding_box() else {
+            return false;
+        };
+        self.centre_on((
+            top_left.0 + (bottom_right.0 - top_left.0) / 2,
+            top_left.1 + (bottom_right.1 - top_left.1) / 2,
+        ));
+        return true;
+    }
+
+    /// The top left and bottom right live cells' corners, inclusive.
+    pub fn bounding_box(&self) -> Option<((i64, i64), (i64, i64))> {
+        let live_cells = self.live_cells();
+        let first = *live_cells.first()?;
+        return Some(live_cells.iter().fold((first, first), |(top_left, bottom_right), (x, y)| {
+            (
+                (top_left.0.min(*x), top_left.1.min(*y)),
+                (bottom_right.0.max(*x), bottom_right.1.max(*y)),
+            )
+        }));
+    }
+
+    /// Halves the frame interval, an uncapped world is already as fast as it goes.
+    pub fn faster(&mut self) {
+        if self.uncapped {
+            return;
         }
+        self.frame_interval_ms = (self.frame_interval_ms / 2).max(1);
+    }
 
-        let original_chunk_x = self.current_chunk_address.0;
-        let original_chunk_y = self.current_chunk_address.1;
-        let mut new_x = original_chunk_x + adjustment_amount.0;
-        let mut new_y = original_chunk_y + adjustment_amount.1;
-        if new_x < 0 { new_x = 0; }
-        if new_x > ((self.x_size / self.chunk_x_size) as i32) - 1 {
-            new_x = ((self.x_size / self.chunk_x_size) as i32) - 1;
+    /// Doubles the frame interval, or drops back to it from uncapped.
+    pub fn slower(&mut self) {
+        if self.uncapped {
+            self.uncapped = false;
+            return;
         }
-        if new_y < 0 { new_y = 0; }
-        if new_y > ((self.y_size / self.chunk_y_size) as i32) - 1 {
-            new_y = ((self.y_size / self.chunk_y_size) as i32) - 1;
+        self.frame_interval_ms = (self.frame_interval_ms * 2).clamp(1, MAX_FRAME_INTERVAL_MS);
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = self.zoom.zoom_in();
+        self.clamp_viewport();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = self.zoom.zoom_out();
+        self.clamp_viewport();
+    }
+
+    /// Keeps the view inside a bounded world, the unbounded plane goes on forever.
+    fn clamp_viewport(&mut self) {
+        if self.storage_kind == StorageKind::Unbounded {
+            return;
         }
-        self.current_chunk_address = (new_x, new_y);
+        let (width, height) = self.viewport_size();
+        let max_x = (self.x_size as i64 - width).max(0);
+        let max_y = (self.y_size as i64 - height).max(0);
+        self.viewport_origin = (
+            self.viewport_origin.0.clamp(0, max_x),
+            self.viewport_origin.1.clamp(0, max_y),
+        );
     }
 
-    pub fn reset_game_world(
+    pub fn reset_game_world<'a>(
         &mut self,
-        tiles_to_set_as_alive: Iter<(u16, u16), bool>,
+        tiles_to_set_as_alive: impl Iterator<Item = (&'a (u16, u16), &'a bool)>,
     ) {
         self.reset_world();
         for key in tiles_to_set_as_alive {
             if *key.1 {
-                let key_post = (key.0.0 as usize, key.0.1 as usize);
-                World::insert_position(&mut self.tiles, &mut self.alive_tile_keys, key_post);
+                self.insert_live_cell((key.0.0 as i64, key.0.1 as i64));
             }
         }
+        self.current_generation = 1;
     }
 
     pub fn handle_generation(
         &mut self,
-        mut keys_to_remove: &mut Vec<(i128, i128)>,
-        mut keys_to_add: &mut Vec<(i128, i128)>,
+        keys_to_remove: &mut Vec<(i128, i128)>,
+        keys_to_add: &mut Vec<(i128, i128)>,
     ) {
+        self.current_generation += 1;
+        match self.storage_kind {
+            StorageKind::Unbounded => {
+                self.sparse_world.handle_generation(&self.rule);
+                return;
+            }
+            StorageKind::BitPacked => {
+                self.bit_grid.handle_generation(&self.rule, self.boundary_mode);
+                return;
+            }
+            StorageKind::Tiles => {}
+        }
+        if self.uses_parallel_generation() {
+            self.handle_parallel_generation();
+            return;
+        }
+
+        // Cells that were already dying keep fading whatever their neighbors are doing.
+        let dying_keys: Vec<(usize, usize)> = self.dying_tile_keys.keys().copied().collect();
+
         let mut keys_to_search = Vec::new();
-        for i in self.alive_tile_keys.keys().into_iter() {
+        for i in self.alive_tile_keys.keys() {
             keys_to_search.push((i.0, i.1));
         }
         self.handle_top_generation(
             keys_to_search,
-            &mut keys_to_remove,
-            &mut keys_to_add,
+            keys_to_remove,
+            keys_to_add,
             0,
         );
         keys_to_remove.clear();
+
+        for key in dying_keys {
+            let next_state = self.rule.next_state(self.tiles[&key].state, 0);
+            self.set_tile_state(key, next_state);
+        }
     }
 
-    #[allow(dead_code)]
-    pub fn insert_blinker_box(mut tiles: &mut HashMap<(usize, usize), Tile>, mut alive_tile_keys: &mut HashMap<(usize, usize), bool>) {
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (5, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (5, 6));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (6, 7));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (7, 7));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (8, 7));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (6, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (5, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (7, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (8, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (9, 5));
-        Self::insert_position(&mut tiles, &mut alive_tile_keys, (9, 6));
-    }
-
-    #[allow(dead_code)]
-    pub fn insert_oscillator(
-        mut tiles: &mut HashMap<(usize, usize), Tile>,
-        mut alive_tile_keys: &mut HashMap<(usize, usize), bool>,
-        position: (usize, usize),
-        orientation: OscillatorOrientation,
-    ) {
-        match orientation {
-            OscillatorOrientation::Vertical => {
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0 + 1, position.1));
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0 + 1, position.1 - 1));
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0 + 1, position.1 - 2));
-            }
-            OscillatorOrientation::Horizontal => {
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0, position.1 - 1));
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0 + 1, position.1 - 1));
-                Self::insert_position(&mut tiles, &mut alive_tile_keys, (position.0 + 2, position.1 - 1));
-            }
+    /// Whether the next tile generation is stepped across `worker_threads` stripes, only
+    /// for worlds big and busy enough that scanning every cell beats visiting the live ones.
+    pub fn uses_parallel_generation(&self) -> bool {
+        let cells = self.x_size * self.y_size;
+        let occupied = self.alive_tile_keys.len() + self.dying_tile_keys.len();
+        return self.worker_threads > 1
+            && cells >= PARALLEL_MIN_CELLS
+            && occupied * PARALLEL_MAX_CELLS_PER_OCCUPIED >= cells;
+    }
+
+    /// Steps the tiles one stripe per worker thread, producing exactly what the serial path
+    /// does.
+    fn handle_parallel_generation(&mut self) {
+        let stripe_rows = ChunkStripe::rows_per_stripe(self.y_size, self.worker_threads);
+        let mut stripes = ChunkStripe::split(self.x_size, self.y_size, self.worker_threads);
+        let occupied_keys = self.alive_tile_keys.keys().chain(self.dying_tile_keys.keys());
+        ChunkStripe::fill(
+            &mut stripes,
+            stripe_rows,
+            occupied_keys.map(|key| (*key, self.tiles[key].state)),
+        );
+        ChunkStripe::exchange_halos(&mut stripes, stripe_rows, self.y_size, self.boundary_mode);
+
+        let changes = ChunkStripe::parallel_changes(&stripes, &self.rule, self.worker_threads);
+        for (x, y, state) in changes {
+            self.set_tile_state((x, y), state);
         }
     }
 
-    #[allow(dead_code)]
-    pub fn self_insert_blinker_box(
-        &mut self,
-        position: (usize, usize),
-    ) -> &mut World {
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 + 6));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 + 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 + 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 + 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 + 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 + 6));
-        return self;
-    }
-
-    #[allow(dead_code)]
-    pub fn self_insert_oscillator(
-        &mut self,
-        position: (usize, usize),
-        orientation: OscillatorOrientation,
-    ) -> &mut World {
-        match orientation {
-            OscillatorOrientation::Vertical => {
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 1, position.1));
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 1, position.1 - 1));
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 1, position.1 - 2));
-            }
-            OscillatorOrientation::Horizontal => {
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0, position.1 - 1));
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 1, position.1 - 1));
-                Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 1));
-            }
+    /// Whether `advance_generations` jumps straight there with HashLife. It only knows two
+    /// states on an unbounded plane, so bounded worlds and Generations rules step instead.
+    pub fn jumps_with_hashlife(&self) -> bool {
+        return self.storage_kind == StorageKind::Unbounded && !self.rule.is_generations();
+    }
+
+    /// Runs `generations` generations, unbounded worlds jump ahead with HashLife while
+    /// bounded ones queue them for `step_pending_generations` so their edges behave exactly
+    /// as usual.
+    pub fn advance_generations(&mut self, generations: u128) {
+        if self.jumps_with_hashlife() {
+            let mut hashlife = match self.hashlife.take() {
+                Some(hashlife) if hashlife.is_in_step_with(self) => hashlife,
+                _ => HashLife::from_world(self),
+            };
+            hashlife.advance(generations);
+            hashlife.write_to_world(self);
+            self.hashlife = Some(hashlife);
+            return;
         }
-        return self;
+        self.pending_generations = self.pending_generations.saturating_add(generations);
     }
 
-    #[allow(dead_code)]
-    pub fn self_insert_chaos_cloverleaf(
-        &mut self,
-        position: (usize, usize),
-    ) -> &mut World {
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 1));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 9));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 8));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 1));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 8));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 9));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 10));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 11, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 13));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 15));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 15));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 13));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 12));
-        return self;
-    }
-
-    #[allow(dead_code)]
-    pub fn self_insert_cloverleaf(
-        &mut self,
-        position: (usize, usize),
-    ) -> &mut World {
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 1));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 7));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 6, position.1 - 9));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 1));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 5));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 2));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 3));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 4));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 8));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 2, position.1 - 9));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 10));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 3, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 13));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 4, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 5, position.1 - 15));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 7, position.1 - 15));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 14));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 13));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 10, position.1 - 12));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 9, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 11));
-        Self::insert_position(&mut self.tiles, &mut self.alive_tile_keys, (position.0 + 8, position.1 - 12));
-        return self;
+    /// Reads a generation count typed into the advance prompt, counts HashLife can't jump
+    /// in one go are refused on every storage so the prompt behaves the same.
+    pub fn parse_generations(input: &str) -> Result<u128, String> {
+        return match input.trim().parse::<u128>() {
+            Ok(generations) if generations > MAX_GENERATIONS => Err(format!(
+                "Can advance at most {} generations at once, got {}",
+                MAX_GENERATIONS, input
+            )),
+            Ok(generations) if generations > 0 => Ok(generations),
+            _ => Err(format!("Expected a number of generations above 0, got {}", input)),
+        };
+    }
+
+    /// Jumps `2^jump_exponent` generations ahead.
+    pub fn jump(&mut self) {
+        self.advance_generations(1 << self.jump_exponent);
+    }
+
+    pub fn larger_jump(&mut self) {
+        self.jump_exponent = (self.jump_exponent + 1).min(MAX_JUMP_EXPONENT);
+    }
+
+    pub fn smaller_jump(&mut self) {
+        self.jump_exponent = self.jump_exponent.saturating_sub(1);
+    }
+
+    /// Steps queued generations until `budget` runs out, always at least one.
+    pub fn step_pending_generations(&mut self, budget: Duration) {
+        let started = Instant::now();
+        let mut keys_to_remove: Vec<(i128, i128)> = Vec::new();
+        let mut keys_to_add: Vec<(i128, i128)> = Vec::new();
+        while self.pending_generations > 0 {
+            self.handle_generation(&mut keys_to_remove, &mut keys_to_add);
+            self.pending_generations -= 1;
+            if started.elapsed() >= budget {
+                break;
+            }
+        }
     }
 
     fn handle_top_generation(
@@ -333,62 +631,35 @@ impl World {
             let x = i.0;
             let y = i.1;
 
-            live_neighbors = self.calculate_northern_neighbors(
-                &mut dead_keys_to_seen,
-                live_neighbors,
-                x,
-                y,
-            );
-            live_neighbors = self.calculate_western_neighbors(
-                &mut dead_keys_to_seen,
-                live_neighbors,
-                x,
-                y,
-            );
-            live_neighbors = self.calculate_eastern_neighbors(
-                &mut dead_keys_to_seen,
-                live_neighbors,
-                x,
-                y,
-            );
-            live_neighbors = self.calculate_southern_neighbors(
+            live_neighbors = self.calculate_neighbors(
                 &mut dead_keys_to_seen,
                 live_neighbors,
                 x,
                 y,
             );
 
-            let tile = self.tiles.get_mut(&i);
-            if !tile.is_none() {
-                if tile.unwrap().alive {
-                    if live_neighbors < 2 {
-                        keys_to_remove.push((i.0 as i128, i.1 as i128));
-                    }
-                    if live_neighbors == 2 || live_neighbors == 3 {
+            if let Some(tile) = self.tiles.get(&i) {
+                if tile.is_alive() {
+                    if self.rule.survives(live_neighbors) {
                         keys_to_add.push((i.0 as i128, i.1 as i128));
-                    }
-                    if live_neighbors > 3 {
+                    } else {
                         keys_to_remove.push((i.0 as i128, i.1 as i128));
                     }
-                } else {
-                    if live_neighbors == 3 {
-                        keys_to_add.push((i.0 as i128, i.1 as i128));
-                    }
+                } else if tile.state == DEAD && self.rule.is_born(live_neighbors) {
+                    keys_to_add.push((i.0 as i128, i.1 as i128));
                 }
             }
             live_neighbors = 0;
         }
         for i in keys_to_add.iter() {
-            let t = self.tiles.get_mut(&(i.0 as usize, i.1 as usize)).unwrap();
-            t.set_alive(true);
-            self.alive_tile_keys.insert((i.0 as usize, i.1 as usize), true);
+            self.set_tile_state((i.0 as usize, i.1 as usize), ALIVE);
         }
 
         let mut keys_to_search = Vec::new();
         for (key, _) in dead_keys_to_seen.into_iter() {
             keys_to_search.push((key.0 as usize, key.1 as usize));
         }
-        if keys_to_search.len() > 0 && depth == 0 {
+        if !keys_to_search.is_empty() && depth == 0 {
             self.handle_top_generation(
                 keys_to_search,
                 keys_to_remove,
@@ -397,133 +668,36 @@ impl World {
             )
         }
 
+        // Under Generations rules cells that don't survive start dying rather than die.
+        let removed_state = if self.rule.is_generations() { ALIVE + 1 } else { DEAD };
         for i in keys_to_remove.iter() {
-            let t = self.tiles.get_mut(&(i.0 as usize, i.1 as usize)).unwrap();
-            t.set_alive(false);
-            self.alive_tile_keys.remove(&(i.0 as usize, i.1 as usize));
+            self.set_tile_state((i.0 as usize, i.1 as usize), removed_state);
         }
         keys_to_add.clear();
     }
 
-    fn calculate_southern_neighbors(
-        &mut self,
-        mut dead_keys_to_seen: &mut HashMap<(i128, i128), usize>,
-        live_neighbors: usize,
-        x: usize,
-        y: usize,
-    ) -> usize {
-        let mut live_neighbors = live_neighbors;
-        if y > 0 {
-            let s_key = (x, y - 1);
-            let s_neighbor = self.tiles.get_mut(&s_key);
-            if !s_neighbor.is_none() {
-                if s_neighbor.unwrap().alive {
-                    live_neighbors += 1;
-                }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, s_key);
-            }
-            if x > 0 {
-                let sw_key = (x - 1, y - 1);
-                let sw_neighbor = self.tiles.get_mut(&sw_key);
-                if !sw_neighbor.is_none() {
-                    if sw_neighbor.unwrap().alive {
-                        live_neighbors += 1;
-                    }
-                    World::handle_neighbor_key_seen(&mut dead_keys_to_seen, sw_key);
-                }
-            }
-            if x < self.x_size {
-                let se_key = (x + 1, y - 1);
-                let se_neighbor = self.tiles.get_mut(&se_key);
-                if !se_neighbor.is_none() {
-                    if se_neighbor.unwrap().alive {
-                        live_neighbors += 1;
-                    }
-                    World::handle_neighbor_key_seen(&mut dead_keys_to_seen, se_key);
-                }
-            }
-        }
-        return live_neighbors;
-    }
-
-    fn calculate_eastern_neighbors(
-        &mut self,
-        mut dead_keys_to_seen: &mut HashMap<(i128, i128), usize>,
-        live_neighbors: usize,
-        x: usize,
-        y: usize,
-    ) -> usize {
-        let mut live_neighbors = live_neighbors;
-        if x < self.x_size {
-            let e_key = (x + 1, y);
-            let e_neighbor = self.tiles.get_mut(&e_key);
-            if !e_neighbor.is_none() {
-                if e_neighbor.unwrap().alive {
-                    live_neighbors += 1;
-                }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, e_key);
-            }
-        }
-        return live_neighbors;
-    }
-
-    fn calculate_western_neighbors(
-        &mut self,
-        mut dead_keys_to_seen: &mut HashMap<(i128, i128), usize>,
-        live_neighbors: usize,
-        x: usize,
-        y: usize,
-    ) -> usize {
-        let mut live_neighbors = live_neighbors;
-        if x > 0 {
-            let w_key = (x - 1, y);
-            let w_neighbor = self.tiles.get_mut(&w_key);
-            if !w_neighbor.is_none() {
-                if w_neighbor.unwrap().alive {
-                    live_neighbors += 1;
-                }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, w_key);
-            }
-        }
-        return live_neighbors;
-    }
-
-    fn calculate_northern_neighbors(
-        &mut self,
-        mut dead_keys_to_seen: &mut HashMap<(i128, i128), usize>,
+    fn calculate_neighbors(
+        &self,
+        dead_keys_to_seen: &mut HashMap<(i128, i128), usize>,
         live_neighbors: usize,
         x: usize,
         y: usize,
     ) -> usize {
         let mut live_neighbors = live_neighbors;
-        if y < self.y_size {
-            let n_key = (x, y + 1);
-            let n_neighbor = self.tiles.get_mut(&n_key);
-            if !n_neighbor.is_none() {
-                if n_neighbor.unwrap().alive {
-                    live_neighbors += 1;
-                }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, n_key);
-            }
-            if x > 0 {
-                let nw_key = (x - 1, y + 1);
-                let nw_neighbor = self.tiles.get_mut(&nw_key);
-                if !nw_neighbor.is_none() {
-                    if nw_neighbor.unwrap().alive {
-                        live_neighbors += 1;
-                    }
-                }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, nw_key);
-            }
-            if x < self.x_size {
-                let ne_key = (x + 1, y + 1);
-                let ne_neighbor = self.tiles.get_mut(&ne_key);
-                if !ne_neighbor.is_none() {
-                    if ne_neighbor.unwrap().alive {
+        for (x_offset, y_offset) in NEIGHBOR_OFFSETS {
+            let neighbor_key = self.boundary_mode.resolve(
+                x as i64 + x_offset,
+                y as i64 + y_offset,
+                self.x_size,
+                self.y_size,
+            );
+            if let Some(neighbor_key) = neighbor_key {
+                if let Some(neighbor) = self.tiles.get(&neighbor_key) {
+                    if neighbor.is_alive() {
                         live_neighbors += 1;
                     }
+                    World::handle_neighbor_key_seen(dead_keys_to_seen, neighbor_key);
                 }
-                World::handle_neighbor_key_seen(&mut dead_keys_to_seen, ne_key);
             }
         }
         return live_neighbors;
@@ -534,7 +708,7 @@ impl World {
         x: (usize, usize),
     ) {
         let temp_key = (x.0 as i128, x.1 as i128);
-        let dk = dead_keys_to_seen.get(&temp_key).unwrap_or(&(0));
+        let dk = dead_keys_to_seen.get(&temp_key).unwrap_or(&0);
         let dk_count = *dk + 1;
         dead_keys_to_seen.insert(temp_key, dk_count);
     }
@@ -1,6 +1,12 @@
-use crossterm::style::Stylize;
-use crate::tile::Tile;
-use crate::world::World;
+use crossterm::style::Color;
+use crate::frame_buffer::{FrameBuffer, FrameCell};
+use crate::tile::{ALIVE, DEAD};
+use crate::world::{StorageKind, World};
+use crate::zoom::ZoomLevel;
+
+/// Colour of a freshly dying cell, later dying states fade towards `DYING_END_COLOR`.
+const DYING_START_COLOR: (u8, u8, u8) = (230, 120, 20);
+const DYING_END_COLOR: (u8, u8, u8) = (60, 30, 30);
 
 pub enum MoveDirections {
     Left,
@@ -12,45 +18,85 @@ pub enum MoveDirections {
 pub struct GameWorldDisplay { }
 
 impl GameWorldDisplay {
-    pub fn print_chunk(world: &World) {
-        let chunk_address = world.current_chunk_address;
-        print!(
-            "Chunk: ({}, {}) of ({}, {}) :: WorldSize: {}x{} :: TilesVisible: {}\n",
-            chunk_address.0,
-            chunk_address.1,
-            (world.x_size / world.chunk_x_size) - 1,
-            (world.y_size / world.chunk_y_size) - 1,
+    /// The line above the grid describing what's in view.
+    pub fn chunk_header(world: &World) -> String {
+        let origin = world.viewport_origin;
+        let (char_width, char_height) = world.zoom.cells_per_char();
+        if world.storage_kind == StorageKind::Unbounded {
+            return format!(
+                "Viewport: ({}, {}) on the unbounded plane :: Population: {} :: TilesVisible: {} :: Zoom: {} :: Rule: {}",
+                origin.0,
+                origin.1,
+                world.population(),
+                world.chunk_y_size * world.chunk_x_size * char_width * char_height,
+                world.zoom.name(),
+                world.rule
+            );
+        }
+        return format!(
+            "Viewport: ({}, {}) :: WorldSize: {}x{} :: TilesVisible: {} :: Zoom: {} :: Rule: {} :: Boundary: {} :: Storage: {} :: Threads: {}",
+            origin.0,
+            origin.1,
             world.x_size,
             world.y_size,
-            world.chunk_y_size * world.chunk_x_size
+            world.chunk_y_size * world.chunk_x_size * char_width * char_height,
+            world.zoom.name(),
+            world.rule,
+            world.boundary_mode.name(),
+            world.storage_kind.name(),
+            world.worker_threads
         );
-        let iter_chunk_x = world.chunk_x_size - 1;
+    }
+
+    /// Draws the view into `frame`, one character per column starting at row `top`.
+    pub fn draw_chunk(world: &World, frame: &mut FrameBuffer, top: usize) {
+        let origin = world.viewport_origin;
+        let (char_width, char_height) = world.zoom.cells_per_char();
         for y in 0..world.chunk_y_size {
             for x in 0..world.chunk_x_size {
                 let to_fetch = (
-                    x + chunk_address.0 as usize * world.chunk_x_size,
-                    y + chunk_address.1 as usize * world.chunk_y_size
+                    origin.0 + (x * char_width) as i64,
+                    origin.1 + (y * char_height) as i64
                 );
 
-                let tile = world
-                    .tiles
-                    .get(&(to_fetch))
-                    .unwrap_or(&Tile { alive: true });
-
-                if tile.alive {
-                    if x == iter_chunk_x {
-                        print!("{}", format!("X\n").yellow());
-                    } else {
-                        print!("{}", format!("X").yellow());
-                    }
-                } else {
-                    if x == iter_chunk_x {
-                        print!("{}", " \n");
-                    } else {
-                        print!("{}", " ");
+                let cell = match world.zoom {
+                    ZoomLevel::Cells => match world.cell_state(to_fetch.0, to_fetch.1) {
+                        DEAD => FrameCell::BLANK,
+                        ALIVE => FrameCell { glyph: 'X', color: Some(Color::Yellow) },
+                        dying => FrameCell {
+                            glyph: 'x',
+                            color: Some(GameWorldDisplay::dying_color(dying, world.rule.states)),
+                        },
+                    },
+                    zoom => {
+                        let mut alive = Vec::with_capacity(char_width * char_height);
+                        for cell_y in to_fetch.1..to_fetch.1 + char_height as i64 {
+                            for cell_x in to_fetch.0..to_fetch.0 + char_width as i64 {
+                                alive.push(world.cell_state(cell_x, cell_y) == ALIVE);
+                            }
+                        }
+                        match zoom.glyph(&alive) {
+                            ' ' => FrameCell::BLANK,
+                            glyph => FrameCell { glyph, color: Some(Color::Yellow) },
+                        }
                     }
-                }
+                };
+                frame.set(x, top + y, cell);
             }
         }
     }
+
+    /// Blends from the start to the end colour as a cell moves through its dying states.
+    fn dying_color(state: u8, states: u8) -> Color {
+        let dying_states = (states - 2).max(1) as f32;
+        let progress = (state - 2) as f32 / dying_states;
+        let blend = |start: u8, end: u8| {
+            (start as f32 + (end as f32 - start as f32) * progress).round() as u8
+        };
+        return Color::Rgb {
+            r: blend(DYING_START_COLOR.0, DYING_END_COLOR.0),
+            g: blend(DYING_START_COLOR.1, DYING_END_COLOR.1),
+            b: blend(DYING_START_COLOR.2, DYING_END_COLOR.2),
+        };
+    }
 }
@@ -0,0 +1,118 @@
+/// How many cells the game view packs into each terminal character, zooming out goes down
+/// this list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZoomLevel {
+    /// One cell per character, with dying states in colour.
+    #[default]
+    Cells,
+    /// 1x2 cells drawn with the upper and lower half blocks.
+    HalfBlocks,
+    /// 2x2 cells drawn with the quadrant blocks.
+    Quadrants,
+    /// 2x4 cells drawn as Braille dots.
+    Braille,
+    /// A square of cells per character, shaded by how many are alive.
+    Density(usize),
+}
+
+/// The squares the density levels zoom out to.
+const DENSITY_SIDES: [usize; 3] = [4, 8, 16];
+
+/// Quadrant glyphs indexed by top left = 1, top right = 2, bottom left = 4, bottom right = 8.
+const QUADRANT_GLYPHS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Braille dot bits for each cell of a 2x4 block, in row-major order.
+const BRAILLE_DOTS: [u32; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+const DENSITY_SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+impl ZoomLevel {
+    pub fn name(&self) -> String {
+        return match self {
+            ZoomLevel::Cells => "1:1".to_string(),
+            ZoomLevel::HalfBlocks => "half blocks 1x2".to_string(),
+            ZoomLevel::Quadrants => "quadrants 2x2".to_string(),
+            ZoomLevel::Braille => "braille 2x4".to_string(),
+            ZoomLevel::Density(side) => format!("density {}x{}", side, side),
+        };
+    }
+
+    /// The width and height in cells of one character.
+    pub fn cells_per_char(&self) -> (usize, usize) {
+        return match self {
+            ZoomLevel::Cells => (1, 1),
+            ZoomLevel::HalfBlocks => (1, 2),
+            ZoomLevel::Quadrants => (2, 2),
+            ZoomLevel::Braille => (2, 4),
+            ZoomLevel::Density(side) => (*side, *side),
+        };
+    }
+
+    pub fn zoom_out(&self) -> ZoomLevel {
+        return match self {
+            ZoomLevel::Cells => ZoomLevel::HalfBlocks,
+            ZoomLevel::HalfBlocks => ZoomLevel::Quadrants,
+            ZoomLevel::Quadrants => ZoomLevel::Braille,
+            ZoomLevel::Braille => ZoomLevel::Density(DENSITY_SIDES[0]),
+            ZoomLevel::Density(side) => ZoomLevel::Density(
+                DENSITY_SIDES
+                    .into_iter()
+                    .find(|larger| larger > side)
+                    .unwrap_or(*side),
+            ),
+        };
+    }
+
+    pub fn zoom_in(&self) -> ZoomLevel {
+        return match self {
+            ZoomLevel::Cells | ZoomLevel::HalfBlocks => ZoomLevel::Cells,
+            ZoomLevel::Quadrants => ZoomLevel::HalfBlocks,
+            ZoomLevel::Braille => ZoomLevel::Quadrants,
+            ZoomLevel::Density(side) => match DENSITY_SIDES
+                .into_iter()
+                .rev()
+                .find(|smaller| smaller < side)
+            {
+                Some(smaller) => ZoomLevel::Density(smaller),
+                None => ZoomLevel::Braille,
+            },
+        };
+    }
+
+    /// The character for a block of cells, `alive` holds the block's cells row by row.
+    pub fn glyph(&self, alive: &[bool]) -> char {
+        let bits = alive
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .fold(0_usize, |bits, (index, _)| bits | 1 << index);
+        return match self {
+            ZoomLevel::Cells => if bits == 0 { ' ' } else { 'X' },
+            ZoomLevel::HalfBlocks => [' ', '▀', '▄', '█'][bits],
+            ZoomLevel::Quadrants => QUADRANT_GLYPHS[bits],
+            ZoomLevel::Braille => {
+                let dots = BRAILLE_DOTS
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| bits & 1 << index != 0)
+                    .fold(0, |dots, (_, dot)| dots | dot);
+                if dots == 0 {
+                    ' '
+                } else {
+                    char::from_u32(0x2800 + dots).unwrap_or(' ')
+                }
+            }
+            ZoomLevel::Density(_) => {
+                let population = alive.iter().filter(|alive| **alive).count();
+                if population == 0 {
+                    ' '
+                } else {
+                    let shade = (population * 4).div_ceil(alive.len()).clamp(1, 4);
+                    DENSITY_SHADES[shade]
+                }
+            }
+        };
+    }
+}